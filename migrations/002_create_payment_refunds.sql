@@ -0,0 +1,16 @@
+-- 创建退款记录表
+CREATE TABLE IF NOT EXISTS payment_refunds (
+    id CHAR(36) PRIMARY KEY COMMENT '退款ID (UUID)',
+    order_id CHAR(36) NOT NULL COMMENT '支付订单ID',
+    out_order_no VARCHAR(64) NOT NULL COMMENT '商户订单号',
+    out_refund_no VARCHAR(64) NOT NULL UNIQUE COMMENT '商户退款单号',
+    refund_id VARCHAR(64) NULL COMMENT '微信退款单号',
+    amount_cents BIGINT NOT NULL COMMENT '退款金额（分）',
+    reason VARCHAR(80) NULL COMMENT '退款原因',
+    state VARCHAR(50) NOT NULL COMMENT '退款状态: processing, succeeded, closed, abnormal',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
+
+    INDEX idx_order_id (order_id),
+    INDEX idx_out_order_no (out_order_no)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='退款记录表';
@@ -0,0 +1,4 @@
+-- 支付订单增加所属商户号
+ALTER TABLE payment_orders
+    ADD COLUMN merchant_id VARCHAR(32) NOT NULL DEFAULT '' COMMENT '所属商户号' AFTER id,
+    ADD INDEX idx_merchant_id (merchant_id);
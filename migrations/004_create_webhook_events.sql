@@ -0,0 +1,12 @@
+-- 创建回调通知审计表（保存脱敏后的解密报文，需开启 PERSIST_WEBHOOK_PAYLOADS）
+CREATE TABLE IF NOT EXISTS webhook_events (
+    id CHAR(36) PRIMARY KEY COMMENT '记录ID (UUID)',
+    notification_id VARCHAR(64) NOT NULL COMMENT '微信通知ID',
+    event_type VARCHAR(64) NOT NULL COMMENT '通知类型',
+    out_order_no VARCHAR(64) NULL COMMENT '商户订单号',
+    payload TEXT NOT NULL COMMENT '脱敏后的解密报文',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '接收时间',
+
+    INDEX idx_notification_id (notification_id),
+    INDEX idx_out_order_no (out_order_no)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='回调通知审计表';
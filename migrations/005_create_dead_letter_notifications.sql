@@ -0,0 +1,12 @@
+-- 创建死信通知表（无法处理的回调通知，保存原始报文供人工排查）
+CREATE TABLE IF NOT EXISTS dead_letter_notifications (
+    id CHAR(36) PRIMARY KEY COMMENT '记录ID (UUID)',
+    notification_id VARCHAR(64) NOT NULL COMMENT '微信通知ID',
+    event_type VARCHAR(64) NOT NULL COMMENT '通知类型',
+    reason TEXT NOT NULL COMMENT '失败原因',
+    raw_body TEXT NOT NULL COMMENT '原始请求体',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '记录时间',
+
+    INDEX idx_notification_id (notification_id),
+    INDEX idx_created_at (created_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='死信通知表';
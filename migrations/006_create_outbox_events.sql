@@ -0,0 +1,16 @@
+-- 创建发件箱事件表（与业务数据在同一事务中写入，由后台任务投递）
+CREATE TABLE IF NOT EXISTS outbox_events (
+    id CHAR(36) PRIMARY KEY COMMENT '事件ID (UUID)',
+    event_type VARCHAR(64) NOT NULL COMMENT '事件类型',
+    aggregate_id VARCHAR(64) NOT NULL COMMENT '聚合ID（商户订单号）',
+    payload TEXT NOT NULL COMMENT '事件内容（JSON）',
+    state VARCHAR(16) NOT NULL DEFAULT 'pending' COMMENT '投递状态: pending/published/poison',
+    attempts INT UNSIGNED NOT NULL DEFAULT 0 COMMENT '已尝试投递次数',
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '下次投递时间',
+    last_error TEXT NULL COMMENT '最近一次投递失败原因',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    published_at TIMESTAMP NULL COMMENT '投递成功时间',
+
+    INDEX idx_state_next_attempt (state, next_attempt_at),
+    INDEX idx_aggregate_id (aggregate_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='发件箱事件表';
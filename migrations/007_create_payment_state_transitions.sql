@@ -0,0 +1,12 @@
+-- 创建订单状态变更记录表（与订单状态在同一事务中写入，用于审计）
+CREATE TABLE IF NOT EXISTS payment_state_transitions (
+    seq BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY COMMENT '写入顺序',
+    id CHAR(36) NOT NULL COMMENT '记录ID (UUID)',
+    order_id CHAR(36) NOT NULL COMMENT '订单ID',
+    from_state VARCHAR(20) NOT NULL COMMENT '变更前状态',
+    to_state VARCHAR(20) NOT NULL COMMENT '变更后状态',
+    created_at TIMESTAMP(6) NOT NULL COMMENT '变更时间',
+
+    UNIQUE KEY uk_id (id),
+    INDEX idx_order_id (order_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='订单状态变更记录表';
@@ -0,0 +1,4 @@
+-- 支付订单增加重试来源（重新发起支付时关联被重试的原订单）
+ALTER TABLE payment_orders
+    ADD COLUMN parent_out_order_no VARCHAR(64) NULL COMMENT '被重试的原商户订单号' AFTER prepay_id,
+    ADD INDEX idx_parent_out_order_no (parent_out_order_no);
@@ -0,0 +1,4 @@
+-- 支付订单增加优惠标记（代金券活动批次，用于活动报表）
+ALTER TABLE payment_orders
+    ADD COLUMN goods_tag VARCHAR(32) NULL COMMENT '订单优惠标记' AFTER parent_out_order_no,
+    ADD INDEX idx_goods_tag (goods_tag);
@@ -0,0 +1,3 @@
+-- 支付订单增加 Native 支付二维码链接
+ALTER TABLE payment_orders
+    ADD COLUMN code_url VARCHAR(64) NULL COMMENT 'Native 支付二维码链接' AFTER goods_tag;
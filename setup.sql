@@ -7,6 +7,7 @@ USE payment_db;
 -- 创建支付订单表
 CREATE TABLE IF NOT EXISTS payment_orders (
     id CHAR(36) PRIMARY KEY COMMENT '订单ID (UUID)',
+    merchant_id VARCHAR(32) NOT NULL DEFAULT '' COMMENT '所属商户号',
     out_order_no VARCHAR(64) NOT NULL UNIQUE COMMENT '商户订单号',
     transaction_id VARCHAR(64) NULL COMMENT '微信支付交易号',
     amount_cents BIGINT NOT NULL COMMENT '支付金额（分）',
@@ -20,12 +21,91 @@ CREATE TABLE IF NOT EXISTS payment_orders (
     paid_at TIMESTAMP NULL COMMENT '支付完成时间',
     attach TEXT NULL COMMENT '附加数据',
     prepay_id VARCHAR(64) NULL COMMENT '微信预下单ID',
+    parent_out_order_no VARCHAR(64) NULL COMMENT '被重试的原商户订单号',
+    goods_tag VARCHAR(32) NULL COMMENT '订单优惠标记',
+    code_url VARCHAR(64) NULL COMMENT 'Native 支付二维码链接',
 
+    INDEX idx_merchant_id (merchant_id),
     INDEX idx_out_order_no (out_order_no),
     INDEX idx_transaction_id (transaction_id),
     INDEX idx_state (state),
-    INDEX idx_created_at (created_at)
+    INDEX idx_created_at (created_at),
+    INDEX idx_parent_out_order_no (parent_out_order_no),
+    INDEX idx_goods_tag (goods_tag)
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='支付订单表';
 
+-- 创建退款记录表
+CREATE TABLE IF NOT EXISTS payment_refunds (
+    id CHAR(36) PRIMARY KEY COMMENT '退款ID (UUID)',
+    order_id CHAR(36) NOT NULL COMMENT '支付订单ID',
+    out_order_no VARCHAR(64) NOT NULL COMMENT '商户订单号',
+    out_refund_no VARCHAR(64) NOT NULL UNIQUE COMMENT '商户退款单号',
+    refund_id VARCHAR(64) NULL COMMENT '微信退款单号',
+    amount_cents BIGINT NOT NULL COMMENT '退款金额（分）',
+    reason VARCHAR(80) NULL COMMENT '退款原因',
+    state VARCHAR(50) NOT NULL COMMENT '退款状态: processing, succeeded, closed, abnormal',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
+
+    INDEX idx_order_id (order_id),
+    INDEX idx_out_order_no (out_order_no)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='退款记录表';
+
+-- 创建回调通知审计表（保存脱敏后的解密报文，需开启 PERSIST_WEBHOOK_PAYLOADS）
+CREATE TABLE IF NOT EXISTS webhook_events (
+    id CHAR(36) PRIMARY KEY COMMENT '记录ID (UUID)',
+    notification_id VARCHAR(64) NOT NULL COMMENT '微信通知ID',
+    event_type VARCHAR(64) NOT NULL COMMENT '通知类型',
+    out_order_no VARCHAR(64) NULL COMMENT '商户订单号',
+    payload TEXT NOT NULL COMMENT '脱敏后的解密报文',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '接收时间',
+
+    INDEX idx_notification_id (notification_id),
+    INDEX idx_out_order_no (out_order_no)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='回调通知审计表';
+
+-- 创建死信通知表（无法处理的回调通知，保存原始报文供人工排查）
+CREATE TABLE IF NOT EXISTS dead_letter_notifications (
+    id CHAR(36) PRIMARY KEY COMMENT '记录ID (UUID)',
+    notification_id VARCHAR(64) NOT NULL COMMENT '微信通知ID',
+    event_type VARCHAR(64) NOT NULL COMMENT '通知类型',
+    reason TEXT NOT NULL COMMENT '失败原因',
+    raw_body TEXT NOT NULL COMMENT '原始请求体',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '记录时间',
+
+    INDEX idx_notification_id (notification_id),
+    INDEX idx_created_at (created_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='死信通知表';
+
+-- 创建发件箱事件表（与业务数据在同一事务中写入，由后台任务投递）
+CREATE TABLE IF NOT EXISTS outbox_events (
+    id CHAR(36) PRIMARY KEY COMMENT '事件ID (UUID)',
+    event_type VARCHAR(64) NOT NULL COMMENT '事件类型',
+    aggregate_id VARCHAR(64) NOT NULL COMMENT '聚合ID（商户订单号）',
+    payload TEXT NOT NULL COMMENT '事件内容（JSON）',
+    state VARCHAR(16) NOT NULL DEFAULT 'pending' COMMENT '投递状态: pending/published/poison',
+    attempts INT UNSIGNED NOT NULL DEFAULT 0 COMMENT '已尝试投递次数',
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '下次投递时间',
+    last_error TEXT NULL COMMENT '最近一次投递失败原因',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    published_at TIMESTAMP NULL COMMENT '投递成功时间',
+
+    INDEX idx_state_next_attempt (state, next_attempt_at),
+    INDEX idx_aggregate_id (aggregate_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='发件箱事件表';
+
+-- 创建订单状态变更记录表（与订单状态在同一事务中写入，用于审计）
+CREATE TABLE IF NOT EXISTS payment_state_transitions (
+    seq BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY COMMENT '写入顺序',
+    id CHAR(36) NOT NULL COMMENT '记录ID (UUID)',
+    order_id CHAR(36) NOT NULL COMMENT '订单ID',
+    from_state VARCHAR(20) NOT NULL COMMENT '变更前状态',
+    to_state VARCHAR(20) NOT NULL COMMENT '变更后状态',
+    created_at TIMESTAMP(6) NOT NULL COMMENT '变更时间',
+
+    UNIQUE KEY uk_id (id),
+    INDEX idx_order_id (order_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='订单状态变更记录表';
+
 -- 显示创建的表
 SHOW TABLES;
@@ -0,0 +1,58 @@
+use crate::api::handlers::AppState;
+use crate::application::ErrorResponse;
+use axum::{
+    extract::{Request, State},
+    http::{StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use tracing::warn;
+
+/// 管理接口鉴权：要求 `Authorization: Bearer <ADMIN_TOKEN>`
+pub async fn require_admin<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+>(
+    State(state): State<AppState<T, R>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(expected) = state.admin_token.as_deref() else {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "ADMIN_DISABLED".to_string(),
+                "Admin endpoints are disabled (ADMIN_TOKEN not set)".to_string(),
+            )),
+        )
+            .into_response();
+    };
+
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "));
+
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => {
+            next.run(request).await
+        }
+        _ => {
+            warn!("Rejected admin request to {}", request.uri().path());
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(ErrorResponse::new(
+                    "UNAUTHORIZED".to_string(),
+                    "Invalid or missing admin token".to_string(),
+                )),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// 常量时间比较，避免通过响应时间猜测令牌
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
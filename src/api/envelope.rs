@@ -0,0 +1,66 @@
+use crate::api::handlers::AppState;
+use crate::infrastructure::config::parse_envelope_flag;
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderMap, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tracing::error;
+
+/// 请求头：按请求启用成功响应包装
+pub const ENVELOPE_HEADER: &str = "X-Response-Envelope";
+
+/// 成功响应包装：启用时将 2xx JSON 响应体改写为 `{"success": true, "data": ...}`
+///
+/// 通过 `RESPONSE_ENVELOPE` 全局开启，或由请求头 `X-Response-Envelope: true` 按请求开启。
+/// 错误响应保持原样，不会被重复包装。
+pub async fn wrap_success<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+>(
+    State(state): State<AppState<T, R>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let enabled = state.response_envelope || requested(request.headers());
+    let response = next.run(request).await;
+
+    if !enabled || !response.status().is_success() || !is_json(response.headers()) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let data = match axum::body::to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| {
+            serde_json::from_slice::<serde_json::Value>(&bytes).map_err(|e| e.to_string())
+        }) {
+        Ok(data) => data,
+        Err(e) => {
+            error!("Failed to wrap response body: {}", e);
+            return axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let body = serde_json::json!({ "success": true, "data": data }).to_string();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(body))
+}
+
+fn requested(headers: &HeaderMap) -> bool {
+    headers
+        .get(ENVELOPE_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .and_then(parse_envelope_flag)
+        .unwrap_or(false)
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|h| h.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"))
+}
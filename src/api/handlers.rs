@@ -1,22 +1,69 @@
-use crate::application::{ErrorResponse, PaymentService};
+use crate::api::list_params::ListParams;
+use crate::application::{
+    AmountSummaryResponse, ApiExample, ApiV3KeyRotationResponse, CreatePaymentRequest,
+    DeadLetterResponse, ErrorResponse, PaymentListResponse, PaymentResponse, PaymentService,
+    RefundRequest, RefundResponse, RotateApiV3KeyRequest,
+};
+use crate::application::{csv_export, qr_code};
+use crate::domain::errors::DomainError;
+use crate::infrastructure::Metrics;
+use crate::infrastructure::config::{AppEnvironment, CorsConfig, ListConfig, TimeoutConfig};
+use crate::ports::OrderExportFilter;
 use crate::ports::wechat_pay_port::PaymentNotification;
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Json},
 };
-use tracing::{debug, error, info};
+use serde::Deserialize;
+use tracing::{error, info, warn};
 
 /// 应用状态
 #[derive(Clone)]
 pub struct AppState<T: crate::ports::WeChatPayPort + Clone + 'static, R: crate::ports::PaymentRepositoryPort + Clone + 'static> {
     pub payment_service: std::sync::Arc<PaymentService<T, R>>,
+    pub environment: AppEnvironment,
+    /// 服务指标
+    pub metrics: Metrics,
+    /// 管理接口令牌，未配置时管理接口不可用
+    pub admin_token: Option<String>,
+    /// 浏览器跨域配置
+    pub cors: CorsConfig,
+    /// 是否默认包装成功响应（`{"success": true, "data": ...}`）
+    pub response_envelope: bool,
+    /// 各接口超时
+    pub timeouts: TimeoutConfig,
+    /// 列表分页配置
+    pub list: ListConfig,
+}
+
+impl<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+> axum::extract::FromRef<AppState<T, R>> for ListConfig
+{
+    fn from_ref(state: &AppState<T, R>) -> Self {
+        state.list
+    }
+}
+
+/// 领域错误对应的HTTP状态码（各接口共用，保证同一错误返回相同状态码）
+fn status_for(e: &DomainError) -> StatusCode {
+    match e {
+        DomainError::ValidationError(_) | DomainError::InvalidAmount(_) => StatusCode::BAD_REQUEST,
+        DomainError::OrderNotFound(_) => StatusCode::NOT_FOUND,
+        DomainError::InvalidState { .. } => StatusCode::CONFLICT,
+        DomainError::MerchantMismatch(_) => StatusCode::FORBIDDEN,
+        DomainError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+        DomainError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
 }
 
 /// 创建支付订单
 pub async fn create_payment<T: crate::ports::WeChatPayPort + Clone + 'static, R: crate::ports::PaymentRepositoryPort + Clone + 'static>(
     State(state): State<AppState<T, R>>,
-    Json(request): Json<crate::application::CreatePaymentRequest>,
+    Json(request): Json<CreatePaymentRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
     info!("Received payment creation request: {}", request.out_order_no);
 
@@ -27,17 +74,9 @@ pub async fn create_payment<T: crate::ports::WeChatPayPort + Clone + 'static, R:
         .map(|response| (StatusCode::CREATED, Json(response)).into_response())
         .map_err(|e| {
             error!("Payment creation error: {}", e);
-            let status = match e {
-                crate::domain::errors::DomainError::ValidationError(_) => StatusCode::BAD_REQUEST,
-                crate::domain::errors::DomainError::InvalidAmount(_) => StatusCode::BAD_REQUEST,
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
-            };
-            (
-                status,
-                Json(ErrorResponse::new(
-                    "PAYMENT_ERROR".to_string(),
-                    e.to_string(),
-                )),
+            (
+                status_for(&e),
+                Json(ErrorResponse::from_error("PAYMENT_ERROR", &e)),
             )
         })
 }
@@ -56,74 +95,217 @@ pub async fn query_payment<T: crate::ports::WeChatPayPort + Clone + 'static, R:
         .map(|response| (StatusCode::OK, Json(response)).into_response())
         .map_err(|e| {
             error!("Payment query error: {}", e);
-            let status = match e {
-                crate::domain::errors::DomainError::OrderNotFound(_) => StatusCode::NOT_FOUND,
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
-            };
-            (
-                status,
-                Json(ErrorResponse::new(
-                    "QUERY_ERROR".to_string(),
-                    e.to_string(),
-                )),
+            (
+                status_for(&e),
+                Json(ErrorResponse::from_error("QUERY_ERROR", &e)),
             )
         })
 }
 
-/// 微信支付回调
-pub async fn wechat_webhook<
+/// 返回 Native 订单的 PNG 支付二维码
+pub async fn payment_qr_code<
     T: crate::ports::WeChatPayPort + Clone + 'static,
     R: crate::ports::PaymentRepositoryPort + Clone + 'static,
 >(
     State(state): State<AppState<T, R>>,
-    headers: axum::http::HeaderMap,
-    body: String,
+    Path(out_order_no): Path<String>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
-    info!("Received WeChat payment webhook");
+    info!("Received payment QR code request: {}", out_order_no);
 
-    // 提取签名头
-    let timestamp = headers
-        .get("Wechatpay-Timestamp")
-        .and_then(|h| h.to_str().ok())
-        .ok_or_else(|| {
+    state
+        .payment_service
+        .native_code_url(&out_order_no)
+        .await
+        .and_then(|code_url| qr_code::render_png(&code_url))
+        .map(|png| {
             (
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::new(
-                    "INVALID_SIGNATURE".to_string(),
-                    "Missing Wechatpay-Timestamp".to_string(),
-                )),
+                StatusCode::OK,
+                [(axum::http::header::CONTENT_TYPE, "image/png")],
+                png,
             )
-        })?;
+                .into_response()
+        })
+        .map_err(|e| {
+            error!("Payment QR code error: {}", e);
+            (
+                status_for(&e),
+                Json(ErrorResponse::from_error("QR_CODE_ERROR", &e)),
+            )
+        })
+}
 
-    let nonce = headers
-        .get("Wechatpay-Nonce")
-        .and_then(|h| h.to_str().ok())
-        .ok_or_else(|| {
+/// 关闭订单
+pub async fn close_payment<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+>(
+    State(state): State<AppState<T, R>>,
+    Path(out_order_no): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    info!("Received payment close request: {}", out_order_no);
+
+    state
+        .payment_service
+        .close_payment(&out_order_no)
+        .await
+        .map(|response| (StatusCode::OK, Json(response)).into_response())
+        .map_err(|e| {
+            error!("Payment close error: {}", e);
             (
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::new(
-                    "INVALID_SIGNATURE".to_string(),
-                    "Missing Wechatpay-Nonce".to_string(),
-                )),
+                status_for(&e),
+                Json(ErrorResponse::from_error("CLOSE_ERROR", &e)),
             )
-        })?;
+        })
+}
 
-    let signature = headers
-        .get("Wechatpay-Signature")
-        .and_then(|h| h.to_str().ok())
-        .ok_or_else(|| {
+/// 重新发起失败或已关闭订单的支付
+pub async fn retry_payment<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+>(
+    State(state): State<AppState<T, R>>,
+    Path(out_order_no): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    info!("Received payment retry request: {}", out_order_no);
+
+    state
+        .payment_service
+        .retry_payment(&out_order_no)
+        .await
+        .map(|response| (StatusCode::CREATED, Json(response)).into_response())
+        .map_err(|e| {
+            error!("Payment retry error: {}", e);
             (
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::new(
-                    "INVALID_SIGNATURE".to_string(),
-                    "Missing Wechatpay-Signature".to_string(),
-                )),
+                status_for(&e),
+                Json(ErrorResponse::from_error("RETRY_ERROR", &e)),
             )
+        })
+}
+
+/// 查询订单列表
+pub async fn list_payments<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+>(
+    State(state): State<AppState<T, R>>,
+    ListParams(query): ListParams,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    info!("Received payment list request: page {}", query.page);
+
+    state
+        .payment_service
+        .list_payments(&query)
+        .await
+        .map(|response| (StatusCode::OK, Json(response)).into_response())
+        .map_err(|e| {
+            error!("Payment list error: {}", e);
+            (
+                status_for(&e),
+                Json(ErrorResponse::from_error("LIST_ERROR", &e)),
+            )
+        })
+}
+
+/// 申请退款
+pub async fn refund_payment<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+>(
+    State(state): State<AppState<T, R>>,
+    Path(out_order_no): Path<String>,
+    Json(request): Json<RefundRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    info!("Received refund request: {}", out_order_no);
+
+    state
+        .payment_service
+        .refund_payment(&out_order_no, request.amount, request.reason)
+        .await
+        .map(|response| (StatusCode::CREATED, Json(response)).into_response())
+        .map_err(|e| {
+            error!("Refund error: {}", e);
+            (
+                status_for(&e),
+                Json(ErrorResponse::from_error("REFUND_ERROR", &e)),
+            )
+        })
+}
+
+/// 微信支付回调
+pub async fn wechat_webhook<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+>(
+    State(state): State<AppState<T, R>>,
+    headers: axum::http::HeaderMap,
+    body: String,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    info!("Received WeChat payment webhook");
+
+    // 提取签名头
+    let signature_header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|h| h.to_str().ok())
+            .ok_or_else(|| {
+                state.metrics.record_signature_verification("missing");
+                warn!("Webhook signature header missing: {}", name);
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse::new(
+                        "INVALID_SIGNATURE".to_string(),
+                        format!("Missing {}", name),
+                    )),
+                )
+            })
+    };
+    let serial = signature_header("Wechatpay-Serial")?;
+    let timestamp = signature_header("Wechatpay-Timestamp")?;
+    let nonce = signature_header("Wechatpay-Nonce")?;
+    let signature = signature_header("Wechatpay-Signature")?;
+
+    // 验证签名，防止伪造请求
+    let verification = state
+        .payment_service
+        .verify_notification(serial, timestamp, nonce, &body, signature)
+        .await
+        .map_err(|e| match e {
+            // 平台证书尚未加载（如冷启动），返回 503 让微信稍后重发，避免通知丢失
+            DomainError::CertificateUnavailable(_) => {
+                state.metrics.record_signature_verification("unavailable");
+                warn!("{}, asking WeChat to retry, timestamp: {}", e, timestamp);
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json(ErrorResponse::from_error("CERTIFICATE_UNAVAILABLE", &e)),
+                )
+            }
+            _ => {
+                error!("Failed to verify webhook signature: {}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::from_error("WEBHOOK_ERROR", &e)),
+                )
+            }
         })?;
+    state
+        .metrics
+        .record_signature_verification(verification.label());
 
-    // TODO: 实现签名验证
-    // 实际应用中必须验证签名以防止伪造请求
-    debug!("Webhook signature verification skipped (TODO: implement)");
+    if !verification.is_valid() {
+        warn!(
+            "Webhook signature verification failed ({}), serial: {}, timestamp: {}",
+            verification.label(),
+            serial,
+            timestamp
+        );
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ErrorResponse::new(
+                "INVALID_SIGNATURE".to_string(),
+                "Signature verification failed".to_string(),
+            )),
+        ));
+    }
 
     // 解析通知
     let notification: PaymentNotification = serde_json::from_str(&body).map_err(|e| {
@@ -140,7 +322,7 @@ pub async fn wechat_webhook<
     // 处理通知
     state
         .payment_service
-        .handle_payment_notification(notification)
+        .process_notification(notification, &body)
         .await
         .map(|_| {
             // 返回微信要求的响应格式
@@ -154,15 +336,314 @@ pub async fn wechat_webhook<
             error!("Webhook handling error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(
-                    "WEBHOOK_ERROR".to_string(),
-                    e.to_string(),
-                )),
+                Json(ErrorResponse::from_error("WEBHOOK_ERROR", &e)),
+            )
+        })
+}
+
+/// 按微信支付订单号退款（管理接口）
+pub async fn refund_by_transaction_id<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+>(
+    State(state): State<AppState<T, R>>,
+    Path(transaction_id): Path<String>,
+    Json(request): Json<RefundRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        "Received refund request for transaction: {}",
+        transaction_id
+    );
+
+    state
+        .payment_service
+        .refund_by_transaction_id(&transaction_id, request.amount, request.reason)
+        .await
+        .map(|response| (StatusCode::CREATED, Json(response)).into_response())
+        .map_err(|e| {
+            error!("Refund by transaction error: {}", e);
+            (
+                status_for(&e),
+                Json(ErrorResponse::from_error("REFUND_ERROR", &e)),
+            )
+        })
+}
+
+/// 轮换 API v3 密钥（管理接口）
+pub async fn rotate_api_v3_key<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+>(
+    State(state): State<AppState<T, R>>,
+    Json(request): Json<RotateApiV3KeyRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .payment_service
+        .rotate_api_v3_key(&request.api_v3_key)
+        .await
+        .map(|response| (StatusCode::OK, Json(response)).into_response())
+        .map_err(|e| {
+            error!("api_v3_key rotation error: {}", e);
+            (
+                status_for(&e),
+                Json(ErrorResponse::from_error("KEY_ROTATION_ERROR", &e)),
+            )
+        })
+}
+
+/// 导出订单CSV（管理接口）
+///
+/// 后台任务从仓储流中逐行读取并写入有界通道，响应体随之增量发送，不在内存中物化全部订单。
+pub async fn export_orders<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+>(
+    State(state): State<AppState<T, R>>,
+    Query(params): Query<ExportParams>,
+) -> impl IntoResponse {
+    use futures::StreamExt;
+
+    let filter = OrderExportFilter {
+        merchant_id: params.merchant_id,
+        created_from: params.created_from,
+        created_before: params.created_before,
+    };
+    info!("Exporting orders: {:?}", filter);
+
+    let (tx, rx) = tokio::sync::mpsc::channel::<Result<String, std::io::Error>>(64);
+    let service = state.payment_service.clone();
+    tokio::spawn(async move {
+        if tx
+            .send(Ok(csv_export::ORDER_CSV_HEADER.to_string()))
+            .await
+            .is_err()
+        {
+            return;
+        }
+
+        let mut orders = service.stream_orders(filter);
+        while let Some(order) = orders.next().await {
+            let row = order
+                .map(|order| csv_export::order_csv_row(&order))
+                .map_err(|e| {
+                    error!("Order export failed: {}", e);
+                    std::io::Error::other(e.to_string())
+                });
+            let failed = row.is_err();
+            // 客户端断开或读取失败时停止导出
+            if tx.send(row).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    let body = futures::stream::unfold(
+        rx,
+        |mut rx| async move { rx.recv().await.map(|row| (row, rx)) },
+    );
+
+    (
+        [
+            (axum::http::header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                axum::http::header::CONTENT_DISPOSITION,
+                "attachment; filename=\"orders.csv\"",
+            ),
+        ],
+        axum::body::Body::from_stream(body),
+    )
+}
+
+/// 支付成功金额汇总（管理接口，过滤条件与导出相同）
+pub async fn order_summary<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+>(
+    State(state): State<AppState<T, R>>,
+    Query(params): Query<ExportParams>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let filter = OrderExportFilter {
+        merchant_id: params.merchant_id,
+        created_from: params.created_from,
+        created_before: params.created_before,
+    };
+
+    state
+        .payment_service
+        .sum_succeeded_amount(filter)
+        .await
+        .map(|summary| (StatusCode::OK, Json(summary)).into_response())
+        .map_err(|e| {
+            error!("Order summary error: {}", e);
+            (
+                status_for(&e),
+                Json(ErrorResponse::from_error("SUMMARY_ERROR", &e)),
             )
         })
 }
 
+/// 订单导出参数（时间为 RFC 3339 格式）
+#[derive(Debug, Deserialize)]
+pub struct ExportParams {
+    pub merchant_id: Option<String>,
+    pub created_from: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// 查询死信通知（管理接口）
+pub async fn list_dead_letters<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+>(
+    State(state): State<AppState<T, R>>,
+    Query(params): Query<DeadLetterParams>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 500);
+
+    state
+        .payment_service
+        .list_dead_letters(limit)
+        .await
+        .map(|letters| {
+            let items: Vec<DeadLetterResponse> = letters
+                .iter()
+                .map(DeadLetterResponse::from_letter)
+                .collect();
+            (StatusCode::OK, Json(items)).into_response()
+        })
+        .map_err(|e| {
+            error!("Dead letter list error: {}", e);
+            (
+                status_for(&e),
+                Json(ErrorResponse::from_error("DEAD_LETTER_ERROR", &e)),
+            )
+        })
+}
+
+/// 死信查询参数
+#[derive(Debug, Deserialize)]
+pub struct DeadLetterParams {
+    pub limit: Option<u32>,
+}
+
 /// 健康检查
 pub async fn health_check() -> impl IntoResponse {
     (StatusCode::OK, Json(serde_json::json!({ "status": "ok" })))
 }
+
+/// Prometheus 指标
+pub async fn metrics<
+    T: crate::ports::WeChatPayPort + Clone + 'static,
+    R: crate::ports::PaymentRepositoryPort + Clone + 'static,
+>(
+    State(state): State<AppState<T, R>>,
+) -> impl IntoResponse {
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4",
+        )],
+        state.metrics.render(),
+    )
+}
+
+/// 接口示例（仅非生产环境开放）
+pub async fn api_schema() -> impl IntoResponse {
+    Json(serde_json::json!({
+        "CreatePaymentRequest": CreatePaymentRequest::example(),
+        "PaymentResponse": PaymentResponse::example(),
+        "PaymentListResponse": PaymentListResponse::example(),
+        "AmountSummaryResponse": AmountSummaryResponse::example(),
+        "RefundRequest": RefundRequest::example(),
+        "RefundResponse": RefundResponse::example(),
+        "ApiV3KeyRotationResponse": ApiV3KeyRotationResponse::example(),
+        "ErrorResponse": ErrorResponse::example(),
+        "DeadLetterResponse": DeadLetterResponse::example(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_for_domain_errors() {
+        let cases = [
+            (
+                DomainError::ValidationError("x".into()),
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                DomainError::InvalidAmount("x".into()),
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                DomainError::OrderNotFound("x".into()),
+                StatusCode::NOT_FOUND,
+            ),
+            (
+                DomainError::InvalidState {
+                    expected: "pending".into(),
+                    actual: "closed".into(),
+                },
+                StatusCode::CONFLICT,
+            ),
+            (
+                DomainError::MerchantMismatch("x".into()),
+                StatusCode::FORBIDDEN,
+            ),
+            (
+                DomainError::RateLimited("x".into()),
+                StatusCode::TOO_MANY_REQUESTS,
+            ),
+            (
+                DomainError::ServiceUnavailable("x".into()),
+                StatusCode::SERVICE_UNAVAILABLE,
+            ),
+            (
+                DomainError::InternalError("x".into()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ];
+        for (error, status) in cases {
+            assert_eq!(status_for(&error), status, "{}", error);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_schema_top_level_keys() {
+        let response = api_schema().await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let schema: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        for key in [
+            "CreatePaymentRequest",
+            "PaymentResponse",
+            "PaymentListResponse",
+            "AmountSummaryResponse",
+            "RefundRequest",
+            "RefundResponse",
+            "ApiV3KeyRotationResponse",
+            "ErrorResponse",
+            "DeadLetterResponse",
+        ] {
+            assert!(schema.get(key).is_some(), "missing {}", key);
+        }
+        assert!(schema["PaymentResponse"]["pay_params"]["pay_sign"].is_string());
+        assert_eq!(
+            schema["PaymentResponse"]["refundability"]["refundable"],
+            false
+        );
+        assert_eq!(
+            schema["PaymentResponse"]["refundability"]["reason"],
+            "not_succeeded"
+        );
+        assert!(schema["ErrorResponse"]["message"].is_string());
+        assert!(schema["AmountSummaryResponse"]["total_cents"].is_i64());
+        assert!(schema["ApiV3KeyRotationResponse"]["fingerprint"].is_string());
+    }
+}
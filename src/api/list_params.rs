@@ -0,0 +1,304 @@
+use crate::application::ErrorResponse;
+use crate::domain::{PaymentMethod, PaymentState};
+use crate::infrastructure::config::ListConfig;
+use crate::ports::{OrderListQuery, OrderSortField, SortDirection};
+use axum::{
+    Json, async_trait,
+    extract::{FromRef, FromRequestParts, Query},
+    http::{StatusCode, request::Parts},
+};
+use chrono::{DateTime, Utc};
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer};
+
+/// 列表接口的分页与排序参数
+///
+/// 支持 `page`、`page_size`、`sort`、`order` 四个查询参数：页码和每页数量会被
+/// 限制在合法范围内，排序字段只接受白名单中的列，未知字段返回 400。
+/// 可选的 `merchant_id` 用于多商户模式下按商户过滤。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListParams(pub OrderListQuery);
+
+/// 列表过滤参数：状态与支付方式按枚举取值解析，时间为 RFC 3339 格式，
+/// 解析失败时错误信息带上参数名
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct OrderFilterQuery {
+    #[serde(default, deserialize_with = "state")]
+    pub state: Option<PaymentState>,
+    #[serde(default, deserialize_with = "payment_method")]
+    pub payment_method: Option<PaymentMethod>,
+    #[serde(default, deserialize_with = "created_from")]
+    pub created_from: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "created_before")]
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+fn state<'de, D: Deserializer<'de>>(d: D) -> Result<Option<PaymentState>, D::Error> {
+    named_field("state", d)
+}
+
+fn payment_method<'de, D: Deserializer<'de>>(d: D) -> Result<Option<PaymentMethod>, D::Error> {
+    named_field("payment_method", d)
+}
+
+fn created_from<'de, D: Deserializer<'de>>(d: D) -> Result<Option<DateTime<Utc>>, D::Error> {
+    named_field("created_from", d)
+}
+
+fn created_before<'de, D: Deserializer<'de>>(d: D) -> Result<Option<DateTime<Utc>>, D::Error> {
+    named_field("created_before", d)
+}
+
+/// 将查询参数字符串解析为 `T`，空值视为未设置
+fn named_field<'de, D, T>(field: &str, deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let Some(value) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    T::deserialize(serde::de::value::StrDeserializer::<serde::de::value::Error>::new(&value))
+        .map(Some)
+        .map_err(|e| D::Error::custom(format!("Invalid {}: {}", field, e)))
+}
+
+/// 原始查询参数
+#[derive(Debug, Default, Deserialize)]
+struct RawListParams {
+    merchant_id: Option<String>,
+    page: Option<i64>,
+    page_size: Option<i64>,
+    sort: Option<String>,
+    order: Option<String>,
+}
+
+impl ListParams {
+    fn from_raw(
+        raw: RawListParams,
+        filter: OrderFilterQuery,
+        config: ListConfig,
+    ) -> Result<Self, String> {
+        let page = raw.page.unwrap_or(1).clamp(1, u32::MAX as i64) as u32;
+        let page_size = raw
+            .page_size
+            .unwrap_or(config.default_page_size as i64)
+            .clamp(1, config.max_page_size as i64) as u32;
+
+        let sort = match raw.sort.as_deref() {
+            None | Some("") => OrderSortField::CreatedAt,
+            Some(value) => OrderSortField::parse(value)
+                .ok_or_else(|| format!("Unsupported sort field: {}", value))?,
+        };
+
+        let direction = match raw.order.as_deref() {
+            None | Some("") => SortDirection::Desc,
+            Some(value) => SortDirection::parse(value)
+                .ok_or_else(|| format!("Unsupported sort order: {}", value))?,
+        };
+
+        if let (Some(from), Some(before)) = (filter.created_from, filter.created_before)
+            && from >= before
+        {
+            return Err("Invalid created_from: must be earlier than created_before".to_string());
+        }
+
+        Ok(Self(OrderListQuery {
+            merchant_id: raw.merchant_id.filter(|m| !m.is_empty()),
+            state: filter.state,
+            payment_method: filter.payment_method,
+            created_from: filter.created_from,
+            created_before: filter.created_before,
+            page,
+            page_size,
+            sort,
+            direction,
+        }))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ListParams
+where
+    S: Send + Sync,
+    ListConfig: FromRef<S>,
+{
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let bad_request = |message: String| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(
+                    "INVALID_LIST_PARAMS".to_string(),
+                    message,
+                )),
+            )
+        };
+
+        let Query(raw) = Query::<RawListParams>::from_request_parts(parts, state)
+            .await
+            .map_err(|e| bad_request(e.body_text()))?;
+        let Query(filter) = Query::<OrderFilterQuery>::from_request_parts(parts, state)
+            .await
+            .map_err(|e| bad_request(e.body_text()))?;
+
+        Self::from_raw(raw, filter, ListConfig::from_ref(state)).map_err(bad_request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    async fn extract(uri: &str) -> Result<ListParams, StatusCode> {
+        extract_with(uri, ListConfig::default()).await
+    }
+
+    async fn extract_with(uri: &str, config: ListConfig) -> Result<ListParams, StatusCode> {
+        let (mut parts, _) = Request::get(uri).body(()).unwrap().into_parts();
+        ListParams::from_request_parts(&mut parts, &config)
+            .await
+            .map_err(|(status, _)| status)
+    }
+
+    #[tokio::test]
+    async fn test_defaults() {
+        let ListParams(query) = extract("/api/payments").await.unwrap();
+
+        assert_eq!(query.page, 1);
+        assert_eq!(query.page_size, ListConfig::default().default_page_size);
+        assert_eq!(query.sort, OrderSortField::CreatedAt);
+        assert_eq!(query.direction, SortDirection::Desc);
+    }
+
+    #[tokio::test]
+    async fn test_clamping() {
+        let ListParams(query) = extract("/api/payments?page=0&page_size=1000")
+            .await
+            .unwrap();
+        assert_eq!(query.page, 1);
+        assert_eq!(query.page_size, ListConfig::default().max_page_size);
+
+        let ListParams(query) = extract("/api/payments?page=-3&page_size=-1").await.unwrap();
+        assert_eq!(query.page, 1);
+        assert_eq!(query.page_size, 1);
+    }
+
+    #[tokio::test]
+    async fn test_configured_page_sizes() {
+        let config = ListConfig {
+            default_page_size: 5,
+            max_page_size: 50,
+        };
+
+        let ListParams(query) = extract_with("/api/payments", config).await.unwrap();
+        assert_eq!(query.page_size, 5);
+
+        let ListParams(query) = extract_with("/api/payments?page_size=80", config)
+            .await
+            .unwrap();
+        assert_eq!(query.page_size, 50);
+    }
+
+    #[tokio::test]
+    async fn test_sort_and_order() {
+        let ListParams(query) = extract("/api/payments?page=3&page_size=10&sort=amount&order=ASC")
+            .await
+            .unwrap();
+
+        assert_eq!(query.page, 3);
+        assert_eq!(query.page_size, 10);
+        assert_eq!(query.offset(), 20);
+        assert_eq!(query.sort.column(), "amount_cents");
+        assert_eq!(query.direction, SortDirection::Asc);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_sort_outside_whitelist() {
+        assert_eq!(
+            extract("/api/payments?sort=openid").await,
+            Err(StatusCode::BAD_REQUEST)
+        );
+        assert_eq!(
+            extract("/api/payments?sort=created_at%3B%20DROP%20TABLE%20payment_orders").await,
+            Err(StatusCode::BAD_REQUEST)
+        );
+        assert_eq!(
+            extract("/api/payments?order=desc,amount_cents").await,
+            Err(StatusCode::BAD_REQUEST)
+        );
+    }
+
+    async fn extract_error(uri: &str) -> (StatusCode, String) {
+        let (mut parts, _) = Request::get(uri).body(()).unwrap().into_parts();
+        let (status, Json(body)) =
+            ListParams::from_request_parts(&mut parts, &ListConfig::default())
+                .await
+                .unwrap_err();
+        (status, body.message)
+    }
+
+    #[tokio::test]
+    async fn test_typed_filters() {
+        let ListParams(query) = extract(
+            "/api/payments?state=succeeded&payment_method=native\
+             &created_from=2023-12-01T00:00:00Z&created_before=2024-01-01T08:00:00%2B08:00",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(query.state, Some(PaymentState::Succeeded));
+        assert_eq!(query.payment_method, Some(PaymentMethod::Native));
+        assert_eq!(
+            query.created_from.unwrap().to_rfc3339(),
+            "2023-12-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            query.created_before.unwrap().to_rfc3339(),
+            "2024-01-01T00:00:00+00:00"
+        );
+
+        // 空值视为未设置
+        let ListParams(query) = extract("/api/payments?state=&payment_method=")
+            .await
+            .unwrap();
+        assert_eq!(query.state, None);
+        assert_eq!(query.payment_method, None);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_state() {
+        let (status, message) = extract_error("/api/payments?state=paid").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(message.contains("Invalid state"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_malformed_date() {
+        let (status, message) = extract_error("/api/payments?created_from=2023-12-01").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(message.contains("Invalid created_from"), "{}", message);
+
+        let (status, message) = extract_error(
+            "/api/payments?created_from=2024-01-01T00:00:00Z&created_before=2023-12-01T00:00:00Z",
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(message.contains("created_before"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_non_numeric_page() {
+        assert_eq!(
+            extract("/api/payments?page=abc").await,
+            Err(StatusCode::BAD_REQUEST)
+        );
+    }
+}
@@ -1,5 +1,9 @@
+pub mod admin_auth;
+pub mod envelope;
 pub mod handlers;
+pub mod list_params;
 pub mod routes;
 
-pub use routes::create_router;
 pub use handlers::AppState;
+pub use list_params::ListParams;
+pub use routes::create_router;
@@ -1,16 +1,78 @@
+use super::admin_auth::require_admin;
+use super::envelope::wrap_success;
 use super::handlers::*;
+use crate::infrastructure::config::TimeoutConfig;
 use axum::{
+    Router, middleware,
     routing::{get, post},
-    Router,
 };
 
 pub fn create_router<T: crate::ports::WeChatPayPort + Clone + 'static, R: crate::ports::PaymentRepositoryPort + Clone + 'static>(
     state: AppState<T, R>,
 ) -> Router {
+    let timeouts = state.timeouts;
+    let default_timeout = TimeoutConfig::layer(timeouts.default);
+
+    // 管理接口需要管理员令牌
+    let admin = Router::new()
+        .route("/dead-letters", get(list_dead_letters))
+        .route("/payments/:out_order_no/refunds", post(refund_payment))
+        .route(
+            "/transactions/:transaction_id/refunds",
+            post(refund_by_transaction_id),
+        )
+        .route("/api-v3-key", post(rotate_api_v3_key))
+        .route("/orders/export", get(export_orders))
+        .route("/orders/summary", get(order_summary))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+        .route_layer(default_timeout);
+
+    // 面向浏览器（H5/网页收银台）的接口启用CORS
+    // 创建、查询与列表接口可选启用成功响应包装；列表包含所有商户的订单，需要管理员令牌
+    let mut browser = Router::new()
+        .route(
+            "/api/payments",
+            post(create_payment)
+                .layer(TimeoutConfig::layer(timeouts.create))
+                .merge(
+                    get(list_payments)
+                        .layer(default_timeout)
+                        .route_layer(middleware::from_fn_with_state(state.clone(), require_admin)),
+                ),
+        )
+        .route(
+            "/api/payments/:out_order_no",
+            get(query_payment).layer(TimeoutConfig::layer(timeouts.query)),
+        )
+        .route_layer(middleware::from_fn_with_state(state.clone(), wrap_success))
+        .route(
+            "/api/payments/:out_order_no/qrcode",
+            get(payment_qr_code).layer(default_timeout),
+        )
+        .route(
+            "/api/payments/:out_order_no/close",
+            post(close_payment).layer(default_timeout),
+        )
+        .route(
+            "/api/payments/:out_order_no/retry",
+            post(retry_payment).layer(TimeoutConfig::layer(timeouts.create)),
+        );
+
+    // 接口示例仅在非生产环境开放
+    if !state.environment.is_production() {
+        browser = browser.route("/api/schema", get(api_schema));
+    }
+    let browser = browser.layer(state.cors.layer(state.environment));
+
+    // 回调与管理接口不对浏览器开放，不挂载CORS
     Router::new()
         .route("/health", get(health_check))
-        .route("/api/payments", post(create_payment))
-        .route("/api/payments/:out_order_no", get(query_payment))
-        .route("/api/webhooks/wechat", post(wechat_webhook))
+        .route("/metrics", get(metrics))
+        .route(
+            "/api/webhooks/wechat",
+            post(wechat_webhook).layer(TimeoutConfig::layer(timeouts.webhook)),
+        )
+        .nest("/api/admin", admin)
+        .merge(browser)
         .with_state(state)
 }
@@ -0,0 +1,65 @@
+use crate::domain::PaymentOrder;
+
+/// 订单导出的CSV表头（不包含 openid、客户端IP 等用户信息）
+pub const ORDER_CSV_HEADER: &str = "id,merchant_id,out_order_no,transaction_id,amount_cents,payment_method,state,created_at,paid_at,goods_tag\n";
+
+/// 将订单格式化为一行CSV（含换行符）
+pub fn order_csv_row(order: &PaymentOrder) -> String {
+    let fields = [
+        order.id.to_string(),
+        escape(&order.merchant_id),
+        escape(&order.out_order_no),
+        order
+            .transaction_id
+            .as_deref()
+            .map(escape)
+            .unwrap_or_default(),
+        order.amount.to_cents().to_string(),
+        order.payment_method.to_string(),
+        order.state.to_string(),
+        order.created_at.to_rfc3339(),
+        order.paid_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+        order.goods_tag.as_deref().map(escape).unwrap_or_default(),
+    ];
+    format!("{}\n", fields.join(","))
+}
+
+/// 按 RFC 4180 转义：含逗号、引号或换行的字段用双引号包裹，内部引号加倍
+fn escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Money, PaymentMethod};
+
+    #[test]
+    fn test_row_matches_header_and_escapes_fields() {
+        let order = PaymentOrder::new(
+            "1900000109".to_string(),
+            "ORDER,\"1\"".to_string(),
+            Money::from_cents(1000),
+            PaymentMethod::MiniProgram,
+            "测试商品".to_string(),
+            "127.0.0.1".to_string(),
+            Some("openid123".to_string()),
+            None,
+        )
+        .unwrap();
+
+        let row = order_csv_row(&order);
+        assert!(row.contains(",\"ORDER,\"\"1\"\"\",,1000,mini_program,pending,"));
+        assert!(!row.contains("openid123"));
+        assert_eq!(
+            ORDER_CSV_HEADER.matches(',').count(),
+            row.replace("\"ORDER,\"\"1\"\"\"", "ORDER")
+                .matches(',')
+                .count()
+        );
+    }
+}
@@ -1,14 +1,21 @@
-use crate::domain::value_objects::{Money, PaymentMethod};
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::{Money, PaymentMethod, RefundDenial, Refundability};
+use crate::domain::{DeadLetterNotification, PaymentOrder};
 use crate::ports::wechat_pay_port::MiniProgramPayParams;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// 创建支付请求
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CreatePaymentRequest {
+    /// 商户号（必须为已配置的商户号，缺省使用已配置的商户号）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merchant_id: Option<String>,
+
     /// 商户订单号
     pub out_order_no: String,
 
     /// 支付金额（分）
+    #[serde(deserialize_with = "deserialize_money")]
     pub amount: Money,
 
     /// 支付方式
@@ -18,13 +25,32 @@ pub struct CreatePaymentRequest {
     pub description: String,
 
     /// 用户OpenID（小程序支付时必填）
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub openid: Option<String>,
 
     /// 客户端IP
     pub client_ip: String,
 
     /// 附加数据
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub attach: Option<String>,
+
+    /// 订单优惠标记（参与代金券活动时填写）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goods_tag: Option<String>,
+
+    /// 测试环境下确认发起大额支付
+    #[serde(default)]
+    pub confirm_large_amount: bool,
+}
+
+/// 反序列化外部传入的金额，拒绝负数和超出上限的金额
+fn deserialize_money<'de, D>(deserializer: D) -> Result<Money, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let money = Money::deserialize(deserializer)?;
+    Money::try_from_cents(money.amount_cents).map_err(serde::de::Error::custom)
 }
 
 /// 支付响应
@@ -33,20 +59,204 @@ pub struct PaymentResponse {
     /// 订单ID
     pub order_id: uuid::Uuid,
 
+    /// 商户号
+    pub merchant_id: String,
+
     /// 商户订单号
     pub out_order_no: String,
 
     /// 支付金额（分）
     pub amount: i64,
 
-    /// 预下单ID
-    pub prepay_id: String,
+    /// 预下单ID（尚未向微信预下单时不返回）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prepay_id: Option<String>,
+
+    /// 微信支付订单号（支付成功后返回）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+
+    /// Native 支付二维码链接（仅 Native 支付返回）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_url: Option<String>,
 
     /// 小程序支付参数（仅小程序支付时返回）
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pay_params: Option<MiniProgramPayParams>,
 
     /// 订单状态
     pub state: String,
+
+    /// 微信返回的交易状态描述（仅本次查询访问了微信时返回）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_description: Option<String>,
+
+    /// 被重试的原商户订单号（仅重新发起的订单返回）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_out_order_no: Option<String>,
+
+    /// 可退款情况（仅查询订单时返回）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refundability: Option<RefundabilityResponse>,
+}
+
+/// 订单可退款情况
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RefundabilityResponse {
+    /// 是否可以退款
+    pub refundable: bool,
+
+    /// 剩余可退金额（分，仅可退款时返回）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_amount: Option<i64>,
+
+    /// 不可退款原因：not_succeeded / fully_refunded / too_old
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<RefundDenial>,
+}
+
+impl From<Refundability> for RefundabilityResponse {
+    fn from(refundability: Refundability) -> Self {
+        match refundability {
+            Refundability::Allowed { max } => Self {
+                refundable: true,
+                max_amount: Some(max.to_cents()),
+                reason: None,
+            },
+            Refundability::Denied { reason } => Self {
+                refundable: false,
+                max_amount: None,
+                reason: Some(reason),
+            },
+        }
+    }
+}
+
+impl PaymentResponse {
+    /// 由订单构建响应（不含支付参数和状态描述）
+    pub fn from_order(order: &PaymentOrder) -> Self {
+        Self {
+            order_id: order.id,
+            merchant_id: order.merchant_id.clone(),
+            out_order_no: order.out_order_no.clone(),
+            amount: order.amount.to_cents(),
+            prepay_id: order.prepay_id.clone(),
+            transaction_id: order.transaction_id.clone(),
+            code_url: order.code_url.clone(),
+            pay_params: None,
+            state: order.state.to_string(),
+            state_description: None,
+            parent_out_order_no: order.parent_out_order_no.clone(),
+            refundability: None,
+        }
+    }
+}
+
+/// 订单列表响应
+#[derive(Debug, Serialize)]
+pub struct PaymentListResponse {
+    /// 当前页订单
+    pub items: Vec<PaymentResponse>,
+
+    /// 页码
+    pub page: u32,
+
+    /// 每页数量
+    pub page_size: u32,
+
+    /// 订单总数
+    pub total: u64,
+}
+
+/// 支付成功金额汇总响应
+#[derive(Debug, Serialize)]
+pub struct AmountSummaryResponse {
+    /// 订单数
+    pub count: u64,
+
+    /// 金额合计（分，精确值）
+    pub total_cents: i64,
+
+    /// 金额合计（元，按配置的舍入方式格式化，仅用于展示）
+    pub total_yuan: String,
+}
+
+/// 退款请求
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RefundRequest {
+    /// 退款金额（分）
+    #[serde(deserialize_with = "deserialize_money")]
+    pub amount: Money,
+
+    /// 退款原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// API v3 密钥轮换请求（不实现 Debug，避免密钥进入日志）
+#[derive(Deserialize)]
+pub struct RotateApiV3KeyRequest {
+    pub api_v3_key: String,
+}
+
+/// API v3 密钥轮换响应
+#[derive(Debug, Serialize)]
+pub struct ApiV3KeyRotationResponse {
+    /// 新密钥指纹
+    pub fingerprint: String,
+
+    /// 轮换时间
+    pub rotated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 退款响应
+#[derive(Debug, Serialize)]
+pub struct RefundResponse {
+    /// 退款ID
+    pub refund_id: uuid::Uuid,
+
+    /// 商户订单号
+    pub out_order_no: String,
+
+    /// 商户退款单号
+    pub out_refund_no: String,
+
+    /// 微信退款单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wechat_refund_id: Option<String>,
+
+    /// 退款金额（分）
+    pub amount: i64,
+
+    /// 退款状态
+    pub state: String,
+
+    /// 退款后的订单状态
+    pub order_state: String,
+}
+
+/// 死信通知响应
+#[derive(Debug, Serialize)]
+pub struct DeadLetterResponse {
+    pub id: uuid::Uuid,
+    pub notification_id: String,
+    pub event_type: String,
+    pub reason: String,
+    pub raw_body: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl DeadLetterResponse {
+    pub fn from_letter(letter: &DeadLetterNotification) -> Self {
+        Self {
+            id: letter.id,
+            notification_id: letter.notification_id.clone(),
+            event_type: letter.event_type.clone(),
+            reason: letter.reason.clone(),
+            raw_body: letter.raw_body.clone(),
+            created_at: letter.created_at,
+        }
+    }
 }
 
 /// 错误响应
@@ -54,10 +264,287 @@ pub struct PaymentResponse {
 pub struct ErrorResponse {
     pub error: String,
     pub message: String,
+
+    /// 以相同参数重试是否安全
+    pub retryable: bool,
+
+    /// 建议的重试等待时间（毫秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_ms: Option<u64>,
 }
 
 impl ErrorResponse {
     pub fn new(error: String, message: String) -> Self {
-        Self { error, message }
+        Self {
+            error,
+            message,
+            retryable: false,
+            retry_after_ms: None,
+        }
+    }
+
+    /// 根据领域错误构造错误响应，并带上重试提示
+    pub fn from_error(error: &str, e: &DomainError) -> Self {
+        Self {
+            error: error.to_string(),
+            message: e.to_string(),
+            retryable: e.is_retryable(),
+            retry_after_ms: e.retry_after_ms(),
+        }
+    }
+}
+
+/// 接口示例数据（用于向接入方展示请求/响应结构）
+pub trait ApiExample {
+    fn example() -> Self;
+}
+
+impl ApiExample for CreatePaymentRequest {
+    fn example() -> Self {
+        Self {
+            merchant_id: None,
+            out_order_no: "ORDER20231227001".to_string(),
+            amount: Money::from_cents(1000),
+            payment_method: PaymentMethod::MiniProgram,
+            description: "测试商品".to_string(),
+            openid: Some("oUpF8uMuAJO_M2pxb1Q9zNjWeS6o".to_string()),
+            client_ip: "127.0.0.1".to_string(),
+            attach: None,
+            goods_tag: None,
+            confirm_large_amount: false,
+        }
+    }
+}
+
+impl ApiExample for MiniProgramPayParams {
+    fn example() -> Self {
+        Self {
+            time_stamp: "1703637600".to_string(),
+            nonce_str: "5K8264ILTKCH16CQ2502SI8ZNMTM67VS".to_string(),
+            package: "prepay_id=wx201410272009395522657a690389285100".to_string(),
+            sign_type: "RSA".to_string(),
+            pay_sign: "oR9d8PuhnIc+YZ8cBHFCwfgpaK9gd7vaRvkYD7rthRAZ...".to_string(),
+        }
+    }
+}
+
+impl ApiExample for PaymentResponse {
+    fn example() -> Self {
+        Self {
+            order_id: uuid::Uuid::nil(),
+            merchant_id: "1900000109".to_string(),
+            out_order_no: "ORDER20231227001".to_string(),
+            amount: 1000,
+            prepay_id: Some("wx201410272009395522657a690389285100".to_string()),
+            transaction_id: None,
+            code_url: None,
+            pay_params: Some(MiniProgramPayParams::example()),
+            state: "pending".to_string(),
+            state_description: None,
+            parent_out_order_no: None,
+            refundability: Some(
+                Refundability::Denied {
+                    reason: RefundDenial::NotSucceeded,
+                }
+                .into(),
+            ),
+        }
+    }
+}
+
+impl ApiExample for PaymentListResponse {
+    fn example() -> Self {
+        let mut item = PaymentResponse::example();
+        item.pay_params = None;
+        item.refundability = None;
+        Self {
+            items: vec![item],
+            page: 1,
+            page_size: 20,
+            total: 1,
+        }
+    }
+}
+
+impl ApiExample for AmountSummaryResponse {
+    fn example() -> Self {
+        Self {
+            count: 3,
+            total_cents: 3050,
+            total_yuan: "30.50".to_string(),
+        }
+    }
+}
+
+impl ApiExample for RefundRequest {
+    fn example() -> Self {
+        Self {
+            amount: Money::from_cents(500),
+            reason: Some("商品已退货".to_string()),
+        }
+    }
+}
+
+impl ApiExample for RefundResponse {
+    fn example() -> Self {
+        Self {
+            refund_id: uuid::Uuid::nil(),
+            out_order_no: "ORDER20231227001".to_string(),
+            out_refund_no: "RF1217752501201407033233368018".to_string(),
+            wechat_refund_id: Some("50000000382019052709732678859".to_string()),
+            amount: 500,
+            state: "succeeded".to_string(),
+            order_state: "succeeded".to_string(),
+        }
+    }
+}
+
+impl ApiExample for ApiV3KeyRotationResponse {
+    fn example() -> Self {
+        Self {
+            fingerprint: "9f86d081884c7d65".to_string(),
+            rotated_at: chrono::DateTime::UNIX_EPOCH,
+        }
+    }
+}
+
+impl ApiExample for ErrorResponse {
+    fn example() -> Self {
+        Self::new(
+            "PAYMENT_ERROR".to_string(),
+            "Validation error: Description must be 1-127 bytes, got 0".to_string(),
+        )
+    }
+}
+
+impl ApiExample for DeadLetterResponse {
+    fn example() -> Self {
+        Self::from_letter(&DeadLetterNotification::new(
+            "EV-2018022511223320873".to_string(),
+            "TRANSACTION.SUCCESS".to_string(),
+            "Payment order not found: ORDER20231227001".to_string(),
+            r#"{"id":"EV-2018022511223320873","event_type":"TRANSACTION.SUCCESS"}"#.to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn refund_request(amount_cents: i64) -> serde_json::Result<RefundRequest> {
+        serde_json::from_value(json!({
+            "amount": { "amount_cents": amount_cents },
+            "reason": "测试"
+        }))
+    }
+
+    #[test]
+    fn test_request_amount_deserializes() {
+        assert_eq!(refund_request(500).unwrap().amount.to_cents(), 500);
+    }
+
+    #[test]
+    fn test_request_rejects_negative_amount() {
+        assert!(refund_request(-1).is_err());
+
+        let mut body = serde_json::to_value(CreatePaymentRequest::example()).unwrap();
+        body["amount"]["amount_cents"] = json!(-1000);
+        assert!(serde_json::from_value::<CreatePaymentRequest>(body).is_err());
+    }
+
+    #[test]
+    fn test_request_rejects_overflow_amount() {
+        assert!(refund_request(Money::MAX_CENTS + 1).is_err());
+        assert!(refund_request(i64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_error_response_carries_retry_metadata() {
+        let body = serde_json::to_value(ErrorResponse::from_error(
+            "QUERY_ERROR",
+            &DomainError::RateLimited("FREQUENCY_LIMITED".to_string()),
+        ))
+        .unwrap();
+        assert_eq!(body["retryable"], true);
+        assert_eq!(body["retry_after_ms"], 1000);
+
+        let body = serde_json::to_value(ErrorResponse::from_error(
+            "PAYMENT_ERROR",
+            &DomainError::InvalidState {
+                expected: "pending".to_string(),
+                actual: "succeeded".to_string(),
+            },
+        ))
+        .unwrap();
+        assert_eq!(body["retryable"], false);
+        assert!(body.get("retry_after_ms").is_none());
+    }
+
+    fn new_order() -> PaymentOrder {
+        PaymentOrder::new(
+            "1900000109".to_string(),
+            "ORDER123".to_string(),
+            Money::from_cents(1000),
+            PaymentMethod::Native,
+            "测试商品".to_string(),
+            "127.0.0.1".to_string(),
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_payment_response_omits_absent_fields() {
+        let body = serde_json::to_value(PaymentResponse::from_order(&new_order())).unwrap();
+        let fields = body.as_object().unwrap();
+
+        for field in [
+            "prepay_id",
+            "transaction_id",
+            "pay_params",
+            "state_description",
+        ] {
+            assert!(!fields.contains_key(field), "{} should be omitted", field);
+        }
+        assert_eq!(body["state"], "pending");
+    }
+
+    #[test]
+    fn test_request_dtos_omit_absent_fields() {
+        let request = CreatePaymentRequest {
+            merchant_id: None,
+            openid: None,
+            attach: None,
+            goods_tag: None,
+            ..CreatePaymentRequest::example()
+        };
+        let body = serde_json::to_value(request).unwrap();
+        let fields = body.as_object().unwrap();
+        for field in ["merchant_id", "openid", "attach", "goods_tag"] {
+            assert!(!fields.contains_key(field), "{} should be omitted", field);
+        }
+
+        let body = serde_json::to_value(RefundRequest {
+            reason: None,
+            ..RefundRequest::example()
+        })
+        .unwrap();
+        assert!(!body.as_object().unwrap().contains_key("reason"));
+    }
+
+    #[test]
+    fn test_payment_response_does_not_fake_empty_prepay_id() {
+        let mut order = new_order();
+        let response = PaymentResponse::from_order(&order);
+        assert_eq!(response.prepay_id, None);
+
+        order.set_prepay_id("wx_prepay_123".to_string()).unwrap();
+        order.mark_as_succeeded("TX123".to_string()).unwrap();
+        let body = serde_json::to_value(PaymentResponse::from_order(&order)).unwrap();
+        assert_eq!(body["prepay_id"], "wx_prepay_123");
+        assert_eq!(body["transaction_id"], "TX123");
     }
 }
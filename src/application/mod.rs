@@ -1,5 +1,14 @@
+pub mod csv_export;
 pub mod dto;
+pub mod outbox_relay;
 pub mod payment_service;
+pub mod qr_code;
+pub mod redaction;
+pub mod service_config;
 
 pub use dto::*;
-pub use payment_service::PaymentService;
+pub use outbox_relay::{OutboxRelay, OutboxRelayConfig, RelayReport};
+pub use payment_service::{
+    PaymentService, ReconcileReport, StartupHealReport, StateConsistencyReport,
+};
+pub use service_config::{AmountGuard, MinAmounts, PaymentServiceConfig};
@@ -0,0 +1,320 @@
+use crate::domain::OutboxState;
+use crate::domain::errors::DomainResult;
+use crate::ports::{EventPublisherPort, PaymentRepositoryPort};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// 发件箱投递配置
+#[derive(Debug, Clone)]
+pub struct OutboxRelayConfig {
+    /// 每轮投递的事件数量
+    pub batch_size: u32,
+
+    /// 最大投递次数，超过后转为 poison 状态
+    pub max_attempts: u32,
+
+    /// 首次重试的等待时间，之后按指数增长
+    pub base_backoff: Duration,
+
+    /// 重试等待时间上限
+    pub max_backoff: Duration,
+}
+
+impl Default for OutboxRelayConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            max_attempts: 10,
+            base_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+impl OutboxRelayConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let var = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+
+        Self {
+            batch_size: var("OUTBOX_BATCH_SIZE")
+                .map(|v| v as u32)
+                .unwrap_or(default.batch_size),
+            max_attempts: var("OUTBOX_MAX_ATTEMPTS")
+                .map(|v| v.max(1) as u32)
+                .unwrap_or(default.max_attempts),
+            base_backoff: var("OUTBOX_BASE_BACKOFF_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.base_backoff),
+            max_backoff: var("OUTBOX_MAX_BACKOFF_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.max_backoff),
+        }
+    }
+
+    /// 第 attempt 次失败后的等待时间（指数退避，有上限）
+    pub fn backoff_for(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// 单轮投递结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelayReport {
+    /// 投递成功的事件数
+    pub published: usize,
+
+    /// 投递失败、等待重试的事件数
+    pub retried: usize,
+
+    /// 超过最大次数转为 poison 的事件数
+    pub poisoned: usize,
+
+    /// 最早一条待投递事件的等待时长（无待投递事件时为 None）
+    pub lag: Option<Duration>,
+}
+
+/// 发件箱投递器：将待投递事件发布出去，失败按指数退避重试，单个坏事件不会阻塞后续事件
+pub struct OutboxRelay<P: EventPublisherPort, R: PaymentRepositoryPort> {
+    publisher: Arc<P>,
+    repository: Arc<R>,
+    config: OutboxRelayConfig,
+}
+
+impl<P: EventPublisherPort, R: PaymentRepositoryPort> OutboxRelay<P, R> {
+    pub fn new(publisher: Arc<P>, repository: Arc<R>, config: OutboxRelayConfig) -> Self {
+        Self {
+            publisher,
+            repository,
+            config,
+        }
+    }
+
+    /// 执行一轮投递
+    pub async fn relay_once(&self, now: DateTime<Utc>) -> DomainResult<RelayReport> {
+        let events = self
+            .repository
+            .find_due_outbox_events(now, self.config.batch_size)
+            .await?;
+        debug!("Relaying {} outbox events", events.len());
+
+        let mut report = RelayReport::default();
+        for mut event in events {
+            match self.publisher.publish(&event).await {
+                Ok(()) => {
+                    event.mark_published(now);
+                    report.published += 1;
+                }
+                Err(e) => {
+                    let backoff = self.config.backoff_for(event.attempts + 1);
+                    let retry_at = chrono::Duration::from_std(backoff)
+                        .ok()
+                        .and_then(|delay| now.checked_add_signed(delay))
+                        .unwrap_or(now);
+                    event.record_failure(e.to_string(), retry_at, self.config.max_attempts);
+
+                    if event.state == OutboxState::Poison {
+                        error!(
+                            "Outbox event {} ({}) poisoned after {} attempts: {}",
+                            event.id, event.event_type, event.attempts, e
+                        );
+                        report.poisoned += 1;
+                    } else {
+                        warn!(
+                            "Outbox event {} failed (attempt {}), retrying in {:?}: {}",
+                            event.id, event.attempts, backoff, e
+                        );
+                        report.retried += 1;
+                    }
+                }
+            }
+
+            // 单条更新失败不影响其余事件，下轮会重新投递
+            if let Err(e) = self.repository.update_outbox_event(&event).await {
+                warn!("Failed to update outbox event {}: {}", event.id, e);
+            }
+        }
+
+        report.lag = self
+            .repository
+            .oldest_pending_outbox_event()
+            .await?
+            .map(|created_at| (now - created_at).to_std().unwrap_or_default());
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::Money;
+    use crate::domain::{OutboxEvent, PaymentFailed, PaymentMethod, PaymentOrder};
+    use crate::infrastructure::adapters::{InMemoryPaymentRepository, MockEventPublisher};
+    use crate::ports::UnitOfWork;
+
+    fn config() -> OutboxRelayConfig {
+        OutboxRelayConfig {
+            batch_size: 100,
+            max_attempts: 3,
+            base_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+
+    fn relay() -> (
+        OutboxRelay<MockEventPublisher, InMemoryPaymentRepository>,
+        Arc<MockEventPublisher>,
+        Arc<InMemoryPaymentRepository>,
+    ) {
+        let publisher = Arc::new(MockEventPublisher::new());
+        let repository = Arc::new(InMemoryPaymentRepository::new());
+        let relay = OutboxRelay::new(publisher.clone(), repository.clone(), config());
+        (relay, publisher, repository)
+    }
+
+    async fn enqueue(repository: &InMemoryPaymentRepository, out_order_no: &str) {
+        let order = PaymentOrder::new(
+            "1900000109".to_string(),
+            out_order_no.to_string(),
+            Money::from_yuan(10),
+            PaymentMethod::MiniProgram,
+            "测试商品".to_string(),
+            "127.0.0.1".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
+        let event = OutboxEvent::new(
+            out_order_no,
+            &PaymentFailed::new(&order, "PAYERROR".to_string()),
+        )
+        .unwrap();
+
+        let mut work = repository.begin().await.unwrap();
+        work.save_outbox_event(&event).await.unwrap();
+        work.commit().await.unwrap();
+    }
+
+    fn event_for(repository: &InMemoryPaymentRepository, out_order_no: &str) -> OutboxEvent {
+        repository
+            .outbox_events()
+            .into_iter()
+            .find(|e| e.aggregate_id == out_order_no)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let config = config();
+        assert_eq!(config.backoff_for(1), Duration::from_secs(1));
+        assert_eq!(config.backoff_for(2), Duration::from_secs(2));
+        assert_eq!(config.backoff_for(4), Duration::from_secs(8));
+        assert_eq!(config.backoff_for(100), Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_relay_publishes_pending_events() {
+        let (relay, publisher, repository) = relay();
+        enqueue(&repository, "ORDER1").await;
+        enqueue(&repository, "ORDER2").await;
+
+        let report = relay.relay_once(Utc::now()).await.unwrap();
+
+        assert_eq!(report.published, 2);
+        assert_eq!(report.lag, None);
+        assert_eq!(publisher.published().len(), 2);
+        assert!(
+            repository
+                .outbox_events()
+                .iter()
+                .all(|e| e.state == OutboxState::Published && e.published_at.is_some())
+        );
+
+        // 已投递的事件不会重复投递
+        let report = relay.relay_once(Utc::now()).await.unwrap();
+        assert_eq!(report.published, 0);
+    }
+
+    #[tokio::test]
+    async fn test_relay_backs_off_after_transient_failure() {
+        let (relay, publisher, repository) = relay();
+        enqueue(&repository, "ORDER1").await;
+        publisher.fail_next(1);
+
+        let now = Utc::now();
+        let report = relay.relay_once(now).await.unwrap();
+        assert_eq!(report.retried, 1);
+        assert!(report.lag.is_some());
+
+        let event = event_for(&repository, "ORDER1");
+        assert_eq!(event.state, OutboxState::Pending);
+        assert_eq!(event.attempts, 1);
+        assert_eq!(event.next_attempt_at, now + chrono::Duration::seconds(1));
+        assert!(event.last_error.is_some());
+
+        // 退避期内不重试
+        let report = relay.relay_once(now).await.unwrap();
+        assert_eq!(
+            report,
+            RelayReport {
+                lag: report.lag,
+                ..RelayReport::default()
+            }
+        );
+
+        let report = relay
+            .relay_once(now + chrono::Duration::seconds(1))
+            .await
+            .unwrap();
+        assert_eq!(report.published, 1);
+        assert_eq!(event_for(&repository, "ORDER1").attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_relay_poisons_event_after_max_attempts_and_continues() {
+        let (relay, publisher, repository) = relay();
+        enqueue(&repository, "BAD_ORDER").await;
+        enqueue(&repository, "ORDER1").await;
+        publisher.fail_aggregate("BAD_ORDER");
+
+        let mut now = Utc::now();
+        for _ in 0..3 {
+            relay.relay_once(now).await.unwrap();
+            now += chrono::Duration::minutes(5);
+        }
+
+        let poisoned = event_for(&repository, "BAD_ORDER");
+        assert_eq!(poisoned.state, OutboxState::Poison);
+        assert_eq!(poisoned.attempts, 3);
+        assert!(poisoned.last_error.unwrap().contains("BAD_ORDER"));
+
+        // 坏事件不阻塞其他事件，且不计入投递延迟
+        assert_eq!(
+            event_for(&repository, "ORDER1").state,
+            OutboxState::Published
+        );
+        let report = relay.relay_once(now).await.unwrap();
+        assert_eq!(report.lag, None);
+    }
+
+    #[tokio::test]
+    async fn test_relay_reports_lag_of_oldest_pending_event() {
+        let (relay, publisher, repository) = relay();
+        enqueue(&repository, "ORDER1").await;
+        publisher.fail_next(1);
+
+        let created_at = event_for(&repository, "ORDER1").created_at;
+        let report = relay
+            .relay_once(created_at + chrono::Duration::seconds(30))
+            .await
+            .unwrap();
+
+        assert_eq!(report.lag, Some(Duration::from_secs(30)));
+    }
+}
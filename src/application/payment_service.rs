@@ -1,15 +1,84 @@
-use crate::application::dto::{CreatePaymentRequest, PaymentResponse};
-use crate::domain::errors::DomainResult;
-use crate::domain::PaymentOrder;
-use crate::ports::PaymentRepositoryPort;
+use crate::application::dto::{
+    AmountSummaryResponse, ApiV3KeyRotationResponse, CreatePaymentRequest, PaymentListResponse,
+    PaymentResponse, RefundResponse,
+};
+use crate::application::redaction;
+use crate::application::service_config::PaymentServiceConfig;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::limits;
+use crate::domain::{
+    DeadLetterNotification, Money, OutboxEvent, PaymentFailed, PaymentMethod, PaymentOrder,
+    PaymentRefunded, PaymentState, PaymentSucceeded, RefundFailed, RefundRecord, RefundState,
+    Refundability, StateTransition, WebhookEvent,
+};
 use crate::ports::WeChatPayPort;
+use crate::ports::wechat_pay_port::{
+    CloseOutcome, MiniProgramPayParams, OrderQueryResponse, SignatureVerification,
+    TRADE_STATE_ORDER_NOT_EXIST, WeChatRefundRequest,
+};
+use crate::ports::{
+    OrderExportFilter, OrderListQuery, OrderStream, PaymentRepositoryPort, PendingCursor,
+    UnitOfWork, WorkFuture,
+};
+use chrono::{DateTime, DurationRound, Utc};
 use std::sync::Arc;
-use tracing::{debug, error, info};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// 一次对账的统计结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReconcileReport {
+    /// 处理的时间窗口数
+    pub windows: usize,
+    /// 扫描的未完成订单数
+    pub scanned: usize,
+    /// 状态发生变化的订单数
+    pub reconciled: usize,
+}
+
+/// 启动时修复中断订单的统计结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StartupHealReport {
+    /// 扫描的未完成订单数
+    pub scanned: usize,
+    /// 重新取得预下单结果的订单数
+    pub prepay_recovered: usize,
+    /// 按微信状态同步为终态的订单数
+    pub reconciled: usize,
+    /// 微信侧不存在、在本地关闭的订单数
+    pub closed: usize,
+    /// 修复失败的订单数
+    pub failed: usize,
+    /// 因进程关闭未处理的订单数
+    pub skipped: usize,
+}
+
+/// 订单状态与状态变更记录的一致性校验结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateConsistencyReport {
+    /// 商户订单号
+    pub out_order_no: String,
+    /// 订单表中保存的状态
+    pub stored_state: PaymentState,
+    /// 按状态变更记录重放得到的状态
+    pub derived_state: PaymentState,
+    /// 状态变更记录条数
+    pub transitions: usize,
+    /// 发现的不一致之处（为空表示一致）
+    pub discrepancies: Vec<String>,
+}
+
+impl StateConsistencyReport {
+    pub fn is_consistent(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
 
 /// 支付服务
 pub struct PaymentService<T: WeChatPayPort, R: PaymentRepositoryPort> {
     wechat_pay: Arc<T>,
     repository: Arc<R>,
+    config: PaymentServiceConfig,
 }
 
 impl<T: WeChatPayPort, R: PaymentRepositoryPort> PaymentService<T, R> {
@@ -17,18 +86,119 @@ impl<T: WeChatPayPort, R: PaymentRepositoryPort> PaymentService<T, R> {
         Self {
             wechat_pay,
             repository,
+            config: PaymentServiceConfig::default(),
         }
     }
 
+    /// 设置服务配置
+    pub fn with_config(mut self, config: PaymentServiceConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// 在同一工作单元中执行多个写操作，全部成功才提交，任一失败则回滚
+    async fn unit_of_work<V, F>(&self, work: F) -> DomainResult<V>
+    where
+        V: Send,
+        F: for<'a> FnOnce(&'a mut R::Work) -> WorkFuture<'a, V> + Send,
+    {
+        let mut uow = self.repository.begin().await?;
+        let value = work(&mut uow).await?;
+        uow.commit().await?;
+        Ok(value)
+    }
+
+    /// 在同一事务中落库支付成功结果、状态变更记录与 PaymentSucceeded 发件箱事件
+    async fn persist_succeeded(
+        &self,
+        order: &PaymentOrder,
+        from: PaymentState,
+    ) -> DomainResult<()> {
+        let event = OutboxEvent::new(&order.out_order_no, &PaymentSucceeded::from_order(order))?;
+        let transition = StateTransition::new(order, from);
+        let order = order.clone();
+        self.unit_of_work(move |uow| {
+            Box::pin(async move {
+                uow.set_transaction(&order).await?;
+                uow.save_transition(&transition).await?;
+                uow.save_outbox_event(&event).await
+            })
+        })
+        .await
+    }
+
+    /// 在同一事务中落库支付失败结果、状态变更记录与 PaymentFailed 发件箱事件
+    async fn persist_failed(
+        &self,
+        order: &PaymentOrder,
+        from: PaymentState,
+        reason: String,
+    ) -> DomainResult<()> {
+        let event = OutboxEvent::new(&order.out_order_no, &PaymentFailed::new(order, reason))?;
+        let transition = StateTransition::new(order, from);
+        let order = order.clone();
+        self.unit_of_work(move |uow| {
+            Box::pin(async move {
+                uow.update_state(&order).await?;
+                uow.save_transition(&transition).await?;
+                uow.save_outbox_event(&event).await
+            })
+        })
+        .await
+    }
+
+    /// 在同一事务中落库订单状态与状态变更记录
+    async fn persist_state(&self, order: &PaymentOrder, from: PaymentState) -> DomainResult<()> {
+        let transition = StateTransition::new(order, from);
+        let order = order.clone();
+        self.unit_of_work(move |uow| {
+            Box::pin(async move {
+                uow.update_state(&order).await?;
+                uow.save_transition(&transition).await
+            })
+        })
+        .await
+    }
+
     /// 创建支付订单
     pub async fn create_payment(
         &self,
         request: CreatePaymentRequest,
     ) -> DomainResult<PaymentResponse> {
-        info!("Creating payment for order: {}", request.out_order_no);
+        let merchant_id = match (
+            request.merchant_id.clone(),
+            &self.config.default_merchant_id,
+        ) {
+            (Some(merchant_id), Some(configured)) if &merchant_id != configured => {
+                return Err(DomainError::ValidationError(format!(
+                    "Unknown merchant id: {}",
+                    merchant_id
+                )));
+            }
+            (Some(merchant_id), _) => merchant_id,
+            (None, Some(configured)) => configured.clone(),
+            (None, None) => {
+                return Err(DomainError::ValidationError(
+                    "Merchant id is required".to_string(),
+                ));
+            }
+        };
+
+        info!(merchant_id = %merchant_id, "Creating payment for order: {}", request.out_order_no);
+
+        // 测试环境大额保护
+        if let Some(guard) = &self.config.amount_guard {
+            guard.check(request.amount, request.confirm_large_amount)?;
+        }
+
+        // 支付方式最低金额
+        self.config
+            .min_amounts
+            .check(request.payment_method, request.amount)?;
 
         // 1. 创建领域对象
-        let mut order = PaymentOrder::new(
+        let order = PaymentOrder::new(
+            merchant_id,
             request.out_order_no.clone(),
             request.amount,
             request.payment_method,
@@ -36,20 +206,92 @@ impl<T: WeChatPayPort, R: PaymentRepositoryPort> PaymentService<T, R> {
             request.client_ip,
             request.openid,
             request.attach,
-        )?;
+        )?
+        .with_goods_tag(request.goods_tag)?;
+
+        self.submit_order(order).await
+    }
+
+    /// 重新发起失败或已关闭订单的支付：以 `{原订单号}_R{n}` 创建关联新订单并返回新的支付参数，
+    /// 原订单保持终态；已有未完成的重试订单时直接返回该订单，避免同一笔购买存在多个可支付订单
+    pub async fn retry_payment(&self, out_order_no: &str) -> DomainResult<PaymentResponse> {
+        let parent = self
+            .repository
+            .find_by_out_order_no(out_order_no)
+            .await?
+            .ok_or_else(|| DomainError::OrderNotFound(out_order_no.to_string()))?;
+
+        let mut attempt = 1;
+        let retry_out_order_no = loop {
+            let candidate = format!("{}_R{}", parent.out_order_no, attempt);
+            match self.repository.find_by_out_order_no(&candidate).await? {
+                None => break candidate,
+                Some(child) if !child.is_finished() => {
+                    info!(
+                        merchant_id = %child.merchant_id,
+                        "Order {} already has a pending retry {}", out_order_no, child.out_order_no
+                    );
+                    return Ok(PaymentResponse {
+                        pay_params: self.pay_params(&child).await?,
+                        ..PaymentResponse::from_order(&child)
+                    });
+                }
+                Some(_) => attempt += 1,
+            }
+        };
+        let order = PaymentOrder::retry_of(&parent, retry_out_order_no)?;
+
+        info!(
+            merchant_id = %order.merchant_id,
+            "Retrying payment {} as {}", out_order_no, order.out_order_no
+        );
+        self.submit_order(order).await
+    }
 
+    /// 保存新订单并向微信预下单，返回支付参数
+    async fn submit_order(&self, mut order: PaymentOrder) -> DomainResult<PaymentResponse> {
         // 2. 保存到数据库
         self.repository.save(&order).await?;
         debug!("Order saved to database: {}", order.id);
 
-        // 3. 调用微信支付API
+        // 3. 调用微信支付API并保存预下单结果
+        self.prepay(&mut order).await?;
+
+        // 4. 生成小程序支付参数
+        let pay_params = self.pay_params(&order).await?;
+
+        info!(merchant_id = %order.merchant_id, "Payment created successfully: {}", order.id);
+
+        Ok(PaymentResponse {
+            pay_params,
+            ..PaymentResponse::from_order(&order)
+        })
+    }
+
+    /// 按预下单ID生成支付参数（没有 prepay_id 的订单如 Native 支付不返回）
+    async fn pay_params(&self, order: &PaymentOrder) -> DomainResult<Option<MiniProgramPayParams>> {
+        match &order.prepay_id {
+            Some(prepay_id) => Ok(Some(
+                self.wechat_pay
+                    .generate_mini_pay_params(prepay_id, order.payment_method)
+                    .await?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// 向微信预下单并保存结果（Native 支付返回二维码链接而非 prepay_id）；
+    /// 相同参数重复下单时微信返回同一预下单结果
+    async fn prepay(&self, order: &mut PaymentOrder) -> DomainResult<()> {
         let wechat_request = crate::ports::wechat_pay_port::WeChatPayRequest {
             out_order_no: order.out_order_no.clone(),
+            payment_method: order.payment_method,
             description: order.description.clone(),
             amount_cents: order.amount.to_cents(),
             openid: order.openid.clone(),
             client_ip: order.client_ip.clone(),
             attach: order.attach.clone(),
+            goods_tag: order.goods_tag.clone(),
         };
 
         let wechat_response = self
@@ -57,26 +299,13 @@ impl<T: WeChatPayPort, R: PaymentRepositoryPort> PaymentService<T, R> {
             .create_mini_program_order(wechat_request)
             .await?;
 
-        // 4. 更新预下单ID
-        order.set_prepay_id(wechat_response.prepay_id.clone())?;
-        self.repository.update(&order).await?;
-
-        // 5. 生成小程序支付参数
-        let pay_params = self
-            .wechat_pay
-            .generate_mini_pay_params(&wechat_response.prepay_id)
-            .await?;
-
-        info!("Payment created successfully: {}", order.id);
-
-        Ok(PaymentResponse {
-            order_id: order.id,
-            out_order_no: order.out_order_no,
-            amount: order.amount.to_cents(),
-            prepay_id: wechat_response.prepay_id,
-            pay_params: Some(pay_params),
-            state: order.state.to_string(),
-        })
+        if let Some(prepay_id) = wechat_response.prepay_id {
+            order.set_prepay_id(prepay_id)?;
+        }
+        if let Some(code_url) = wechat_response.code_url {
+            order.set_code_url(code_url)?;
+        }
+        self.repository.set_prepay_id(order).await
     }
 
     /// 查询订单
@@ -93,38 +322,633 @@ impl<T: WeChatPayPort, R: PaymentRepositoryPort> PaymentService<T, R> {
             })?;
 
         // 2. 如果订单未完成，向微信查询最新状态
+        let mut state_description = None;
         if !order.is_finished() {
-            debug!("Order not finished, querying WeChat: {}", out_order_no);
-            let query_response = self.wechat_pay.query_order(out_order_no).await?;
-
-            match query_response.trade_state.as_str() {
-                "SUCCESS" => {
-                    if let Some(tx_id) = query_response.transaction_id {
-                        order.mark_as_succeeded(tx_id)?;
-                        self.repository.update(&order).await?;
+            state_description = self.sync_with_wechat(&mut order).await?;
+        }
+
+        // 3. 计算可退款情况，只有支付成功的订单需要累计已退金额
+        let refunded = if order.state == PaymentState::Succeeded {
+            effective_refund_total(&self.repository.find_refunds_by_order(order.id).await?)
+        } else {
+            Money::from_cents(0)
+        };
+
+        Ok(PaymentResponse {
+            state_description,
+            refundability: Some(order.refundability(refunded).into()),
+            ..PaymentResponse::from_order(&order)
+        })
+    }
+
+    /// 查询 Native 订单的二维码链接；非 Native 订单或尚未取得 code_url 时返回状态错误
+    pub async fn native_code_url(&self, out_order_no: &str) -> DomainResult<String> {
+        let order = self
+            .repository
+            .find_by_out_order_no(out_order_no)
+            .await?
+            .ok_or_else(|| DomainError::OrderNotFound(out_order_no.to_string()))?;
+
+        if order.payment_method != PaymentMethod::Native {
+            return Err(DomainError::InvalidState {
+                expected: "native order".to_string(),
+                actual: order.payment_method.to_string(),
+            });
+        }
+
+        order.code_url.ok_or_else(|| DomainError::InvalidState {
+            expected: "native order with code_url".to_string(),
+            actual: "native order without code_url".to_string(),
+        })
+    }
+
+    /// 关闭订单：重复关闭直接返回；微信返回 ORDERPAID 时说明用户已支付，改为同步订单状态
+    pub async fn close_payment(&self, out_order_no: &str) -> DomainResult<PaymentResponse> {
+        let mut order = self
+            .repository
+            .find_by_out_order_no(out_order_no)
+            .await?
+            .ok_or_else(|| DomainError::OrderNotFound(out_order_no.to_string()))?;
+
+        match order.state {
+            PaymentState::Closed => return Ok(PaymentResponse::from_order(&order)),
+            PaymentState::Succeeded | PaymentState::Refunded => {
+                return Err(DomainError::InvalidState {
+                    expected: "pending or processing or failed".to_string(),
+                    actual: order.state.to_string(),
+                });
+            }
+            _ => {}
+        }
+
+        match self.wechat_pay.close_order(out_order_no).await? {
+            CloseOutcome::Closed => {
+                let from = order.state;
+                order.mark_as_closed()?;
+                self.persist_state(&order, from).await?;
+                info!(merchant_id = %order.merchant_id, "Order closed: {}", out_order_no);
+            }
+            CloseOutcome::AlreadyPaid => {
+                warn!(
+                    merchant_id = %order.merchant_id,
+                    "Order {} already paid on WeChat, reconciling instead of closing", out_order_no
+                );
+                self.sync_with_wechat(&mut order).await?;
+            }
+        }
+
+        Ok(PaymentResponse::from_order(&order))
+    }
+
+    /// 对账：按 `window` 将创建时间早于 `created_before` 的未完成订单划分为时间窗口，
+    /// 逐窗口以 `page_size` 分页向微信同步，内存中最多只保留一页订单
+    pub async fn reconcile_pending(
+        &self,
+        created_before: DateTime<Utc>,
+        window: chrono::Duration,
+        page_size: u32,
+    ) -> DomainResult<ReconcileReport> {
+        if window <= chrono::Duration::zero() || page_size == 0 {
+            return Err(DomainError::ValidationError(
+                "Reconcile window and page size must be positive".to_string(),
+            ));
+        }
+
+        let mut report = ReconcileReport::default();
+        let Some(oldest) = self
+            .repository
+            .oldest_pending_created_at(created_before)
+            .await?
+        else {
+            return Ok(report);
+        };
+
+        // 窗口按 `window` 对齐（如按天对齐到 UTC 零点），保证每个订单只落入一个窗口
+        let mut window_start = oldest.duration_trunc(window).map_err(|e| {
+            DomainError::ValidationError(format!("Invalid reconcile window: {}", e))
+        })?;
+
+        while window_start < created_before {
+            let window_end = (window_start + window).min(created_before);
+            report.windows += 1;
+            report.reconciled += self
+                .reconcile_window(window_start, window_end, page_size, &mut report.scanned)
+                .await?;
+            window_start = window_end;
+        }
+
+        debug!(
+            "Reconciled {} of {} pending orders across {} windows",
+            report.reconciled, report.scanned, report.windows
+        );
+        Ok(report)
+    }
+
+    /// 键集分页同步单个时间窗口内的未完成订单，返回状态发生变化的订单数
+    async fn reconcile_window(
+        &self,
+        created_from: DateTime<Utc>,
+        created_before: DateTime<Utc>,
+        page_size: u32,
+        scanned: &mut usize,
+    ) -> DomainResult<usize> {
+        let mut reconciled = 0;
+        let mut cursor = None;
+
+        loop {
+            let orders = self
+                .repository
+                .find_pending_in_window(created_from, created_before, cursor, page_size)
+                .await?;
+            let Some(last) = orders.last() else {
+                break;
+            };
+            cursor = Some(PendingCursor::after(last));
+            let last_page = orders.len() < page_size as usize;
+            *scanned += orders.len();
+
+            for mut order in orders {
+                match self.sync_with_wechat(&mut order).await {
+                    Ok(_) if order.is_finished() => reconciled += 1,
+                    Ok(_) => {}
+                    Err(e) => {
+                        warn!(
+                            merchant_id = %order.merchant_id,
+                            "Failed to reconcile order {}: {}", order.out_order_no, e
+                        );
                     }
                 }
-                "CLOSED" => {
-                    order.mark_as_closed()?;
-                    self.repository.update(&order).await?;
+            }
+
+            if last_page {
+                break;
+            }
+        }
+
+        Ok(reconciled)
+    }
+
+    /// 启动时修复上次进程中断留下的订单：扫描创建时间在 `[created_from, created_before)` 内的
+    /// 未完成订单（最多 `limit` 笔），按微信状态同步终态，补取缺失的预下单结果，
+    /// 微信侧不存在的订单在本地关闭。每笔订单处理前检查 `shutdown`，关闭时剩余订单留待下次启动
+    pub async fn heal_interrupted_orders(
+        &self,
+        created_from: DateTime<Utc>,
+        created_before: DateTime<Utc>,
+        limit: u32,
+        shutdown: &CancellationToken,
+    ) -> DomainResult<StartupHealReport> {
+        let orders = self
+            .repository
+            .find_pending_in_window(created_from, created_before, None, limit)
+            .await?;
+
+        let mut report = StartupHealReport {
+            scanned: orders.len(),
+            ..StartupHealReport::default()
+        };
+        for (index, mut order) in orders.into_iter().enumerate() {
+            if shutdown.is_cancelled() {
+                report.skipped = report.scanned - index;
+                info!(
+                    "Startup heal interrupted by shutdown, {} orders left",
+                    report.skipped
+                );
+                break;
+            }
+            if let Err(e) = self.heal_order(&mut order, &mut report).await {
+                report.failed += 1;
+                warn!(
+                    merchant_id = %order.merchant_id,
+                    "Failed to heal order {}: {}", order.out_order_no, e
+                );
+            }
+        }
+
+        debug!("Startup heal finished: {:?}", report);
+        Ok(report)
+    }
+
+    /// 修复单个未完成订单
+    async fn heal_order(
+        &self,
+        order: &mut PaymentOrder,
+        report: &mut StartupHealReport,
+    ) -> DomainResult<()> {
+        let query_response = self.wechat_pay.query_order(&order.out_order_no).await?;
+
+        // 下单请求从未到达微信，用户无法再支付该订单
+        if query_response.trade_state == TRADE_STATE_ORDER_NOT_EXIST {
+            let from = order.state;
+            order.mark_as_closed()?;
+            self.persist_state(order, from).await?;
+            report.closed += 1;
+            info!(
+                merchant_id = %order.merchant_id,
+                "Closed order {} unknown to WeChat", order.out_order_no
+            );
+            return Ok(());
+        }
+
+        self.apply_trade_state(order, query_response).await?;
+        if order.is_finished() {
+            report.reconciled += 1;
+        } else if order.prepay_id.is_none() && order.code_url.is_none() {
+            self.prepay(order).await?;
+            report.prepay_recovered += 1;
+            info!(
+                merchant_id = %order.merchant_id,
+                "Recovered prepay result for order {}", order.out_order_no
+            );
+        }
+        Ok(())
+    }
+
+    /// 向微信查询订单最新状态并落库，返回微信的交易状态描述
+    async fn sync_with_wechat(&self, order: &mut PaymentOrder) -> DomainResult<Option<String>> {
+        debug!(merchant_id = %order.merchant_id, "Order not finished, querying WeChat: {}", order.out_order_no);
+        let query_response = self.wechat_pay.query_order(&order.out_order_no).await?;
+        self.apply_trade_state(order, query_response).await
+    }
+
+    /// 按微信查询结果更新订单状态并落库，返回微信的交易状态描述
+    async fn apply_trade_state(
+        &self,
+        order: &mut PaymentOrder,
+        query_response: OrderQueryResponse,
+    ) -> DomainResult<Option<String>> {
+        let from = order.state;
+
+        match query_response.trade_state.as_str() {
+            "SUCCESS" => {
+                if let Some(tx_id) = query_response.transaction_id {
+                    order.mark_as_succeeded(tx_id)?;
+                    self.persist_succeeded(order, from).await?;
                 }
-                "PAYERROR" => {
-                    order.mark_as_failed()?;
-                    self.repository.update(&order).await?;
+            }
+            "CLOSED" => {
+                order.mark_as_closed()?;
+                self.persist_state(order, from).await?;
+            }
+            "PAYERROR" => {
+                order.mark_as_failed()?;
+                let reason = query_response
+                    .trade_state_desc
+                    .clone()
+                    .unwrap_or_else(|| "PAYERROR".to_string());
+                self.persist_failed(order, from, reason).await?;
+            }
+            _ => {
+                debug!("Order state unchanged: {}", query_response.trade_state);
+            }
+        }
+
+        if order.is_finished() {
+            info!(
+                merchant_id = %order.merchant_id,
+                "Order {} reconciled from WeChat: {}", order.out_order_no, order.state
+            );
+        }
+
+        Ok(query_response.trade_state_desc)
+    }
+
+    /// 分页查询订单列表
+    pub async fn list_payments(&self, query: &OrderListQuery) -> DomainResult<PaymentListResponse> {
+        debug!("Listing payments: {:?}", query);
+
+        let page = self.repository.list_orders(query).await?;
+
+        Ok(PaymentListResponse {
+            items: page
+                .orders
+                .iter()
+                .map(PaymentResponse::from_order)
+                .collect(),
+            page: query.page,
+            page_size: query.page_size,
+            total: page.total,
+        })
+    }
+
+    /// 按微信支付订单号退款（客服只有 transaction_id 时使用）
+    pub async fn refund_by_transaction_id(
+        &self,
+        transaction_id: &str,
+        amount: Money,
+        reason: Option<String>,
+    ) -> DomainResult<RefundResponse> {
+        let order = self
+            .repository
+            .find_by_transaction_id(transaction_id)
+            .await?
+            .ok_or_else(|| DomainError::OrderNotFound(transaction_id.to_string()))?;
+
+        info!(
+            merchant_id = %order.merchant_id,
+            "Resolved transaction {} to order {}", transaction_id, order.out_order_no
+        );
+        self.refund_payment(&order.out_order_no, amount, reason)
+            .await
+    }
+
+    /// 申请退款
+    pub async fn refund_payment(
+        &self,
+        out_order_no: &str,
+        amount: Money,
+        reason: Option<String>,
+    ) -> DomainResult<RefundResponse> {
+        info!("Refunding payment: {} amount: {}", out_order_no, amount);
+
+        // 1. 查找订单
+        let mut order = self
+            .repository
+            .find_by_out_order_no(out_order_no)
+            .await?
+            .ok_or_else(|| DomainError::OrderNotFound(out_order_no.to_string()))?;
+
+        // 2. 校验订单可退款、退款次数与可退金额
+        let refunds = self.repository.find_refunds_by_order(order.id).await?;
+        let remaining = match order.refundability(effective_refund_total(&refunds)) {
+            Refundability::Allowed { max } => max,
+            Refundability::Denied { .. } if order.state != PaymentState::Succeeded => {
+                return Err(DomainError::InvalidState {
+                    expected: PaymentState::Succeeded.to_string(),
+                    actual: order.state.to_string(),
+                });
+            }
+            Refundability::Denied { reason } => {
+                return Err(DomainError::ValidationError(format!(
+                    "Order {} is not refundable: {}",
+                    out_order_no, reason
+                )));
+            }
+        };
+
+        // 微信拒绝受理的退款申请（无微信退款单号）不计入退款次数
+        let submitted = refunds
+            .iter()
+            .filter(|r| r.refund_id.is_some() || r.is_effective())
+            .count();
+        if submitted >= self.config.max_refunds_per_order {
+            return Err(DomainError::ValidationError(format!(
+                "Order {} has reached the maximum of {} refunds",
+                out_order_no, self.config.max_refunds_per_order
+            )));
+        }
+
+        if amount.to_cents() > remaining.to_cents() {
+            return Err(DomainError::InvalidAmount(format!(
+                "Refund amount {} exceeds refundable amount {}",
+                amount, remaining
+            )));
+        }
+
+        // 3. 保存退款记录
+        let out_refund_no = format!("RF{}", uuid::Uuid::new_v4().simple());
+        let mut refund = RefundRecord::new(&order, out_refund_no, amount, reason)?;
+        self.repository.save_refund(&refund).await?;
+        debug!("Refund record saved: {}", refund.out_refund_no);
+
+        // 4. 调用微信退款API
+        let wechat_response = match self
+            .wechat_pay
+            .create_refund(WeChatRefundRequest {
+                out_order_no: order.out_order_no.clone(),
+                out_refund_no: refund.out_refund_no.clone(),
+                reason: refund.reason.clone(),
+                refund_cents: refund.amount.to_cents(),
+                total_cents: order.amount.to_cents(),
+            })
+            .await
+        {
+            Ok(response) => response,
+            // 请求超时时微信可能已受理，保留处理中以免重复退款；其余错误说明未受理，释放可退金额
+            Err(DomainError::HttpError(e)) if e.is_timeout() => {
+                warn!(
+                    merchant_id = %order.merchant_id,
+                    "Refund {} timed out, outcome unknown", refund.out_refund_no
+                );
+                return Err(DomainError::HttpError(e));
+            }
+            Err(e) => {
+                refund.mark_rejected();
+                self.repository.update_refund(&refund).await?;
+                warn!(
+                    merchant_id = %order.merchant_id,
+                    "Refund {} rejected by WeChat: {}", refund.out_refund_no, e
+                );
+                return Err(e);
+            }
+        };
+
+        let refund_state = match wechat_response.status.as_str() {
+            "SUCCESS" => RefundState::Succeeded,
+            "CLOSED" => RefundState::Closed,
+            "ABNORMAL" => RefundState::Abnormal,
+            _ => RefundState::Processing,
+        };
+        refund.apply_result(wechat_response.refund_id, refund_state);
+
+        // 5. 在同一事务中更新退款结果，全额退款时同时更新订单状态
+        let full_refund = refund.is_effective() && amount == remaining;
+        let transition = if full_refund {
+            let from = order.state;
+            order.mark_as_refunded()?;
+            Some(StateTransition::new(&order, from))
+        } else {
+            None
+        };
+        // 退款成功或失败时随退款结果写入发件箱事件，处理中的退款不产生事件
+        let event = match refund.state {
+            RefundState::Succeeded => Some(OutboxEvent::new(
+                &order.out_order_no,
+                &PaymentRefunded::from_refund(&order, &refund),
+            )?),
+            RefundState::Closed | RefundState::Abnormal => Some(OutboxEvent::new(
+                &order.out_order_no,
+                &RefundFailed::new(&order, &refund, wechat_response.status.clone()),
+            )?),
+            _ => None,
+        };
+        let (refund_row, order_row) = (refund.clone(), order.clone());
+        self.unit_of_work(move |uow| {
+            Box::pin(async move {
+                uow.update_refund(&refund_row).await?;
+                if let Some(transition) = transition {
+                    uow.update_state(&order_row).await?;
+                    uow.save_transition(&transition).await?;
                 }
-                _ => {
-                    debug!("Order state unchanged: {}", query_response.trade_state);
+                if let Some(event) = event {
+                    uow.save_outbox_event(&event).await?;
                 }
+                Ok(())
+            })
+        })
+        .await?;
+
+        info!(
+            merchant_id = %order.merchant_id,
+            "Refund created: {} for order {} ({})",
+            refund.out_refund_no, out_order_no, refund.state
+        );
+
+        Ok(RefundResponse {
+            refund_id: refund.id,
+            out_order_no: order.out_order_no,
+            out_refund_no: refund.out_refund_no,
+            wechat_refund_id: refund.refund_id,
+            amount: refund.amount.to_cents(),
+            state: refund.state.to_string(),
+            order_state: order.state.to_string(),
+        })
+    }
+
+    /// 验证回调通知签名
+    pub async fn verify_notification(
+        &self,
+        serial: &str,
+        timestamp: &str,
+        nonce: &str,
+        body: &str,
+        signature: &str,
+    ) -> DomainResult<SignatureVerification> {
+        self.wechat_pay
+            .verify_notification(serial, timestamp, nonce, body, signature)
+            .await
+    }
+
+    /// 处理回调通知，无法处理的通知写入死信表后视为已处理，避免微信反复重试
+    pub async fn process_notification(
+        &self,
+        notification: crate::ports::wechat_pay_port::PaymentNotification,
+        raw_body: &str,
+    ) -> DomainResult<()> {
+        let notification_id = notification.id.clone();
+        let event_type = notification.event_type.clone();
+
+        match self.handle_payment_notification(notification).await {
+            Err(e) if is_unprocessable(&e) => {
+                warn!(
+                    "Notification {} moved to dead letter: {}",
+                    notification_id, e
+                );
+                let letter = DeadLetterNotification::new(
+                    notification_id,
+                    event_type,
+                    e.to_string(),
+                    raw_body.to_string(),
+                );
+                self.repository.save_dead_letter(&letter).await
             }
+            result => result,
         }
+    }
 
-        Ok(PaymentResponse {
-            order_id: order.id,
+    /// 流式读取订单（用于导出），调用方逐条消费
+    pub fn stream_orders(&self, filter: OrderExportFilter) -> OrderStream<'_> {
+        self.repository.stream_orders(filter)
+    }
+
+    /// 汇总支付成功订单的金额：逐条累加整数分，最后按配置的舍入方式格式化一次
+    pub async fn sum_succeeded_amount(
+        &self,
+        filter: OrderExportFilter,
+    ) -> DomainResult<AmountSummaryResponse> {
+        use futures::TryStreamExt;
+
+        let (count, total) =
+            self.repository
+                .stream_orders(filter)
+                .try_filter(|order| futures::future::ready(order.state == PaymentState::Succeeded))
+                .try_fold(
+                    (0_u64, Money::from_cents(0)),
+                    |(count, total), order| async move {
+                        Ok((count + 1, total.checked_add(order.amount)?))
+                    },
+                )
+                .await?;
+
+        Ok(AmountSummaryResponse {
+            count,
+            total_cents: total.to_cents(),
+            total_yuan: total.format_yuan(self.config.report_scale, self.config.report_rounding),
+        })
+    }
+
+    /// 从初始状态（待支付）依次重放订单的状态变更记录，并与订单表中保存的状态比对
+    pub async fn verify_state_consistency(
+        &self,
+        out_order_no: &str,
+    ) -> DomainResult<StateConsistencyReport> {
+        let order = self
+            .repository
+            .find_by_out_order_no(out_order_no)
+            .await?
+            .ok_or_else(|| DomainError::OrderNotFound(out_order_no.to_string()))?;
+        let transitions = self.repository.find_transitions(order.id).await?;
+
+        let mut derived_state = PaymentState::Pending;
+        let mut discrepancies = Vec::new();
+        for (index, transition) in transitions.iter().enumerate() {
+            if transition.from_state != derived_state {
+                discrepancies.push(format!(
+                    "Transition #{} starts from {} but replayed state is {}",
+                    index + 1,
+                    transition.from_state,
+                    derived_state
+                ));
+            }
+            derived_state = transition.to_state;
+        }
+        if derived_state != order.state {
+            discrepancies.push(format!(
+                "Stored state {} does not match replayed state {}",
+                order.state, derived_state
+            ));
+        }
+
+        if !discrepancies.is_empty() {
+            warn!(
+                target: "audit",
+                merchant_id = %order.merchant_id,
+                "State inconsistency for order {}: {}", out_order_no, discrepancies.join("; ")
+            );
+        }
+
+        Ok(StateConsistencyReport {
             out_order_no: order.out_order_no,
-            amount: order.amount.to_cents(),
-            prepay_id: order.prepay_id.unwrap_or_default(),
-            pay_params: None,
-            state: order.state.to_string(),
+            stored_state: order.state,
+            derived_state,
+            transitions: transitions.len(),
+            discrepancies,
+        })
+    }
+
+    /// 查询最近的死信通知
+    pub async fn list_dead_letters(&self, limit: u32) -> DomainResult<Vec<DeadLetterNotification>> {
+        self.repository.list_dead_letters(limit).await
+    }
+
+    /// 运行时轮换 API v3 密钥（管理操作），旧密钥在过渡期内仍可解密
+    pub async fn rotate_api_v3_key(&self, key: &str) -> DomainResult<ApiV3KeyRotationResponse> {
+        if key.len() != limits::API_V3_KEY_BYTES {
+            return Err(DomainError::ValidationError(format!(
+                "api_v3_key must be {} bytes, got {}",
+                limits::API_V3_KEY_BYTES,
+                key.len()
+            )));
+        }
+
+        self.wechat_pay.rotate_api_v3_key(key).await?;
+
+        let fingerprint = redaction::fingerprint(key);
+        info!(target: "audit", fingerprint = %fingerprint, "Rotated api_v3_key");
+
+        Ok(ApiV3KeyRotationResponse {
+            fingerprint,
+            rotated_at: Utc::now(),
         })
     }
 
@@ -148,10 +972,27 @@ impl<T: WeChatPayPort, R: PaymentRepositoryPort> PaymentService<T, R> {
             )
             .await?;
 
-        debug!("Decrypted notification: {}", decrypted);
-
         // 解析JSON
         let data: serde_json::Value = serde_json::from_str(&decrypted)?;
+        let redacted = redaction::redacted(&data);
+        debug!("Decrypted notification: {}", redacted);
+
+        // 按配置保存脱敏报文用于审计，保存失败不影响回调处理
+        if self.config.persist_webhook_payloads {
+            let event = WebhookEvent::new(
+                notification.id.clone(),
+                notification.event_type.clone(),
+                data["out_trade_no"].as_str().map(String::from),
+                redacted,
+            );
+            if let Err(e) = self.repository.save_webhook_event(&event).await {
+                warn!(
+                    "Failed to persist webhook payload {}: {}",
+                    notification.id, e
+                );
+            }
+        }
+
         let out_order_no = data["out_trade_no"]
             .as_str()
             .ok_or_else(|| {
@@ -182,10 +1023,49 @@ impl<T: WeChatPayPort, R: PaymentRepositoryPort> PaymentService<T, R> {
                     })?
                     .to_string();
 
+                // 微信会重复推送成功通知：订单已按同一交易成功时直接应答
+                if matches!(
+                    order.state,
+                    PaymentState::Succeeded | PaymentState::Refunded
+                ) {
+                    if order.transaction_id.as_deref() == Some(transaction_id.as_str()) {
+                        debug!(
+                            "Duplicate success notification for {}, ignoring",
+                            out_order_no
+                        );
+                        return Ok(());
+                    }
+                    return Err(DomainError::ValidationError(format!(
+                        "Order {} already succeeded with a different transaction than {}",
+                        out_order_no, transaction_id
+                    )));
+                }
+
+                // 按配置校验付款人：订单记录了 openid 时，通知中的 payer.openid 必须一致
+                if self.config.require_payer_openid_match
+                    && let Some(expected) = order.openid.as_deref()
+                    && data["payer"]["openid"].as_str() != Some(expected)
+                {
+                    warn!(
+                        target: "audit",
+                        merchant_id = %order.merchant_id,
+                        "Payer openid mismatch on success notification for {}, not marking succeeded",
+                        out_order_no
+                    );
+                    return Err(DomainError::ValidationError(format!(
+                        "Payer openid does not match order {}",
+                        out_order_no
+                    )));
+                }
+
+                let from = order.state;
                 order.mark_as_succeeded(transaction_id)?;
-                self.repository.update(&order).await?;
+                self.persist_succeeded(&order, from).await?;
 
-                info!("Payment succeeded via notification: {}", out_order_no);
+                info!(
+                    merchant_id = %order.merchant_id,
+                    "Payment succeeded via notification: {}", out_order_no
+                );
             }
             _ => {
                 debug!("Unhandled notification event type: {}", notification.event_type);
@@ -195,3 +1075,1305 @@ impl<T: WeChatPayPort, R: PaymentRepositoryPort> PaymentService<T, R> {
         Ok(())
     }
 }
+
+/// 已生效（处理中或成功）的退款总额
+fn effective_refund_total(refunds: &[RefundRecord]) -> Money {
+    Money::from_cents(
+        refunds
+            .iter()
+            .filter(|r| r.is_effective())
+            .map(|r| r.amount.to_cents())
+            .sum(),
+    )
+}
+
+/// 重试也无法成功的回调处理错误（如订单不存在、报文缺少字段）
+fn is_unprocessable(error: &DomainError) -> bool {
+    matches!(
+        error,
+        DomainError::OrderNotFound(_)
+            | DomainError::ValidationError(_)
+            | DomainError::SerializationError(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{PaymentMethod, RefundDenial};
+    use crate::infrastructure::adapters::{InMemoryPaymentRepository, MockWeChatPayAdapter};
+
+    fn service() -> (
+        PaymentService<MockWeChatPayAdapter, InMemoryPaymentRepository>,
+        Arc<MockWeChatPayAdapter>,
+    ) {
+        let wechat_pay = Arc::new(MockWeChatPayAdapter::new());
+        let repository = Arc::new(InMemoryPaymentRepository::new());
+        (
+            PaymentService::new(wechat_pay.clone(), repository),
+            wechat_pay,
+        )
+    }
+
+    /// 构造支付成功通知（模拟适配器的解密直接返回密文，因此这里放入明文）
+    fn success_notification(transaction: serde_json::Value) -> crate::ports::PaymentNotification {
+        crate::ports::PaymentNotification {
+            id: "EV-2018022511223320873".to_string(),
+            event_type: "TRANSACTION.SUCCESS".to_string(),
+            resource: crate::ports::NotificationResource {
+                algorithm: "AEAD_AES_256_GCM".to_string(),
+                ciphertext: transaction.to_string(),
+                nonce: "fdasflkja484".to_string(),
+                associated_data: "transaction".to_string(),
+            },
+            create_time: "2023-12-27T10:00:00+08:00".to_string(),
+        }
+    }
+
+    /// 创建订单并通过查询将其置为支付成功
+    async fn create_succeeded_order(
+        service: &PaymentService<MockWeChatPayAdapter, InMemoryPaymentRepository>,
+        wechat_pay: &MockWeChatPayAdapter,
+        out_order_no: &str,
+    ) {
+        service
+            .create_payment(create_request(out_order_no))
+            .await
+            .unwrap();
+        wechat_pay.set_query_response("SUCCESS", Some("TX123"), None);
+        service.query_payment(out_order_no).await.unwrap();
+    }
+
+    fn create_request(out_order_no: &str) -> CreatePaymentRequest {
+        CreatePaymentRequest {
+            merchant_id: Some("1900000109".to_string()),
+            out_order_no: out_order_no.to_string(),
+            amount: Money::from_cents(1000),
+            payment_method: PaymentMethod::MiniProgram,
+            description: "测试商品".to_string(),
+            openid: Some("openid123".to_string()),
+            client_ip: "127.0.0.1".to_string(),
+            attach: None,
+            goods_tag: None,
+            confirm_large_amount: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_native_payment_stores_code_url() {
+        let (service, _) = service();
+        let mut request = create_request("ORDER123");
+        request.payment_method = PaymentMethod::Native;
+        request.openid = None;
+
+        let response = service.create_payment(request).await.unwrap();
+        assert!(response.pay_params.is_none());
+        assert_eq!(response.prepay_id, None);
+
+        let code_url = service.native_code_url("ORDER123").await.unwrap();
+        assert_eq!(code_url, "weixin://wxpay/bizpayurl?pr=mock_ORDER123");
+        assert_eq!(response.code_url, Some(code_url));
+    }
+
+    #[tokio::test]
+    async fn test_native_code_url_requires_native_order_with_code_url() {
+        let (service, _) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+        let result = service.native_code_url("ORDER123").await;
+        assert!(matches!(result, Err(DomainError::InvalidState { .. })));
+
+        // Native 订单尚未取得 code_url（如预下单失败）
+        let order = PaymentOrder::new(
+            "1900000109".to_string(),
+            "ORDER456".to_string(),
+            Money::from_cents(1000),
+            PaymentMethod::Native,
+            "测试商品".to_string(),
+            "127.0.0.1".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
+        service.repository.save(&order).await.unwrap();
+        let result = service.native_code_url("ORDER456").await;
+        assert!(matches!(result, Err(DomainError::InvalidState { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_goods_tag_persisted_and_validated() {
+        let (service, _) = service();
+        let mut request = create_request("ORDER123");
+        request.goods_tag = Some("WXG_SPRING".to_string());
+        service.create_payment(request).await.unwrap();
+
+        let order = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(order.goods_tag.as_deref(), Some("WXG_SPRING"));
+
+        let mut request = create_request("ORDER456");
+        request.goods_tag = Some("春季券".to_string());
+        let result = service.create_payment(request).await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_query_echoes_state_description_after_remote_query() {
+        let (service, wechat_pay) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+        wechat_pay.set_query_response("USERPAYING", None, Some("用户支付中"));
+
+        let response = service.query_payment("ORDER123").await.unwrap();
+
+        assert_eq!(wechat_pay.query_calls(), 1);
+        assert_eq!(response.state, "pending");
+        assert_eq!(response.state_description.as_deref(), Some("用户支付中"));
+    }
+
+    #[tokio::test]
+    async fn test_query_omits_state_description_when_served_locally() {
+        let (service, wechat_pay) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+        wechat_pay.set_query_response("SUCCESS", Some("TX123"), Some("支付成功"));
+        service.query_payment("ORDER123").await.unwrap();
+
+        // 订单已完成，不再访问微信
+        let response = service.query_payment("ORDER123").await.unwrap();
+
+        assert_eq!(wechat_pay.query_calls(), 1);
+        assert_eq!(response.state, "succeeded");
+        assert_eq!(response.state_description, None);
+    }
+
+    #[tokio::test]
+    async fn test_refund_payment_up_to_max_refunds() {
+        let (service, wechat_pay) = service();
+        let service = service.with_config(PaymentServiceConfig {
+            max_refunds_per_order: 3,
+            ..PaymentServiceConfig::default()
+        });
+        create_succeeded_order(&service, &wechat_pay, "ORDER123").await;
+
+        for _ in 0..3 {
+            let response = service
+                .refund_payment("ORDER123", Money::from_cents(100), None)
+                .await
+                .unwrap();
+            assert_eq!(response.state, "succeeded");
+            assert_eq!(response.order_state, "succeeded");
+        }
+
+        let result = service
+            .refund_payment("ORDER123", Money::from_cents(100), None)
+            .await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_full_refund_marks_order_refunded() {
+        let (service, wechat_pay) = service();
+        create_succeeded_order(&service, &wechat_pay, "ORDER123").await;
+
+        service
+            .refund_payment("ORDER123", Money::from_cents(400), None)
+            .await
+            .unwrap();
+        let response = service
+            .refund_payment("ORDER123", Money::from_cents(600), Some("退货".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.order_state, "refunded");
+    }
+
+    #[tokio::test]
+    async fn test_successful_refund_publishes_payment_refunded() {
+        use crate::application::{OutboxRelay, OutboxRelayConfig};
+        use crate::infrastructure::adapters::MockEventPublisher;
+
+        let (service, wechat_pay) = service();
+        create_succeeded_order(&service, &wechat_pay, "ORDER123").await;
+        let response = service
+            .refund_payment("ORDER123", Money::from_cents(300), None)
+            .await
+            .unwrap();
+
+        let publisher = Arc::new(MockEventPublisher::new());
+        let relay = OutboxRelay::new(
+            publisher.clone(),
+            service.repository.clone(),
+            OutboxRelayConfig::default(),
+        );
+        relay.relay_once(Utc::now()).await.unwrap();
+
+        let refunded: Vec<_> = publisher
+            .published()
+            .into_iter()
+            .filter(|e| e.event_type != "PaymentSucceeded")
+            .collect();
+        assert_eq!(refunded.len(), 1);
+        assert_eq!(refunded[0].event_type, "PaymentRefunded");
+        assert_eq!(refunded[0].aggregate_id, "ORDER123");
+
+        let payload: serde_json::Value = serde_json::from_str(&refunded[0].payload).unwrap();
+        assert_eq!(payload["out_refund_no"], response.out_refund_no);
+        assert_eq!(payload["refund_amount"], 300);
+    }
+
+    #[tokio::test]
+    async fn test_refund_by_transaction_id_resolves_order() {
+        let (service, wechat_pay) = service();
+        create_succeeded_order(&service, &wechat_pay, "ORDER123").await;
+
+        let response = service
+            .refund_by_transaction_id("TX123", Money::from_cents(300), None)
+            .await
+            .unwrap();
+
+        assert_eq!(response.out_order_no, "ORDER123");
+        assert_eq!(response.amount, 300);
+
+        let result = service
+            .refund_by_transaction_id("TX_UNKNOWN", Money::from_cents(300), None)
+            .await;
+        assert!(matches!(result, Err(DomainError::OrderNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_query_reports_refundability() {
+        let (service, wechat_pay) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+        wechat_pay.set_query_response("NOTPAY", None, None);
+
+        let response = service.query_payment("ORDER123").await.unwrap();
+        let refundability = response.refundability.unwrap();
+        assert!(!refundability.refundable);
+        assert_eq!(refundability.reason, Some(RefundDenial::NotSucceeded));
+
+        wechat_pay.set_query_response("SUCCESS", Some("TX123"), None);
+        service.query_payment("ORDER123").await.unwrap();
+        service
+            .refund_payment("ORDER123", Money::from_cents(300), None)
+            .await
+            .unwrap();
+
+        let response = service.query_payment("ORDER123").await.unwrap();
+        let refundability = response.refundability.unwrap();
+        assert!(refundability.refundable);
+        assert_eq!(refundability.max_amount, Some(700));
+    }
+
+    #[tokio::test]
+    async fn test_refund_after_refund_window_rejected() {
+        let (service, wechat_pay) = service();
+        create_succeeded_order(&service, &wechat_pay, "ORDER123").await;
+        let mut order = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+        order.paid_at = Some(Utc::now() - chrono::Duration::days(400));
+        service.repository.set_transaction(&order).await.unwrap();
+
+        let result = service
+            .refund_payment("ORDER123", Money::from_cents(100), None)
+            .await;
+        assert!(
+            matches!(&result, Err(DomainError::ValidationError(m)) if m.contains("too_old")),
+            "{:?}",
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refund_exceeding_paid_amount_rejected() {
+        let (service, wechat_pay) = service();
+        create_succeeded_order(&service, &wechat_pay, "ORDER123").await;
+
+        let result = service
+            .refund_payment("ORDER123", Money::from_cents(1001), None)
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidAmount(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_refund_releases_refundable_amount() {
+        let (service, wechat_pay) = service();
+        create_succeeded_order(&service, &wechat_pay, "ORDER123").await;
+        wechat_pay.set_refund_error(400, r#"{"code":"NOT_ENOUGH","message":"基本账户余额不足"}"#);
+
+        let result = service
+            .refund_payment("ORDER123", Money::from_cents(1000), None)
+            .await;
+        assert!(matches!(result, Err(DomainError::WeChatPayError(_))));
+
+        let order = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+        let refunds = service
+            .repository
+            .find_refunds_by_order(order.id)
+            .await
+            .unwrap();
+        assert_eq!(refunds.len(), 1);
+        assert_eq!(refunds[0].state, RefundState::Closed);
+        assert_eq!(
+            order.refundability(effective_refund_total(&refunds)),
+            Refundability::Allowed {
+                max: Money::from_cents(1000)
+            }
+        );
+
+        // 余额仍可全额退款
+        wechat_pay.clear_refund_error();
+        let response = service
+            .refund_payment("ORDER123", Money::from_cents(1000), None)
+            .await
+            .unwrap();
+        assert_eq!(response.amount, 1000);
+    }
+
+    #[tokio::test]
+    async fn test_state_consistency_replays_transition_log() {
+        let (service, wechat_pay) = service();
+        create_succeeded_order(&service, &wechat_pay, "ORDER123").await;
+        service
+            .refund_payment("ORDER123", Money::from_cents(1000), None)
+            .await
+            .unwrap();
+
+        let report = service.verify_state_consistency("ORDER123").await.unwrap();
+
+        assert!(report.is_consistent(), "{:?}", report.discrepancies);
+        assert_eq!(report.transitions, 2);
+        assert_eq!(report.derived_state, PaymentState::Refunded);
+        assert_eq!(report.stored_state, PaymentState::Refunded);
+    }
+
+    #[tokio::test]
+    async fn test_state_consistency_detects_mismatch() {
+        let (service, _) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+        let mut order = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+
+        // 变更记录声称订单从支付中变为支付成功，而订单表被绕过记录直接改为已关闭
+        let mut paid = order.clone();
+        paid.state = PaymentState::Succeeded;
+        let mut work = service.repository.begin().await.unwrap();
+        work.save_transition(&StateTransition::new(&paid, PaymentState::Processing))
+            .await
+            .unwrap();
+        work.commit().await.unwrap();
+        order.mark_as_closed().unwrap();
+        service.repository.update_state(&order).await.unwrap();
+
+        let report = service.verify_state_consistency("ORDER123").await.unwrap();
+
+        assert!(!report.is_consistent());
+        assert_eq!(report.stored_state, PaymentState::Closed);
+        assert_eq!(report.derived_state, PaymentState::Succeeded);
+        assert_eq!(report.discrepancies.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sum_succeeded_amount_is_exact_in_cents() {
+        let (service, wechat_pay) = service();
+        let service = service.with_config(PaymentServiceConfig {
+            report_scale: 0,
+            report_rounding: crate::domain::RoundingMode::HalfEven,
+            ..PaymentServiceConfig::default()
+        });
+
+        // 1000 笔 0.10 元：按浮点元累加会得到 99.9999999999986
+        for i in 0..1000 {
+            let out_order_no = format!("ORDER{}", i);
+            let mut request = create_request(&out_order_no);
+            request.amount = Money::from_cents(10);
+            service.create_payment(request).await.unwrap();
+            wechat_pay.set_query_response("SUCCESS", Some(&format!("TX{}", i)), None);
+            service.query_payment(&out_order_no).await.unwrap();
+        }
+        let mut request = create_request("ORDER_PENDING");
+        request.amount = Money::from_cents(50);
+        service.create_payment(request).await.unwrap();
+
+        let summary = service
+            .sum_succeeded_amount(OrderExportFilter::default())
+            .await
+            .unwrap();
+
+        assert_eq!(summary.count, 1000);
+        assert_eq!(summary.total_cents, 10_000);
+        assert_eq!(summary.total_yuan, "100");
+    }
+
+    #[tokio::test]
+    async fn test_retry_failed_order_creates_linked_order() {
+        let (service, wechat_pay) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+        wechat_pay.set_query_response("PAYERROR", None, Some("支付失败"));
+        service.query_payment("ORDER123").await.unwrap();
+
+        let response = service.retry_payment("ORDER123").await.unwrap();
+
+        assert_eq!(response.out_order_no, "ORDER123_R1");
+        assert_eq!(response.parent_out_order_no.as_deref(), Some("ORDER123"));
+        assert_eq!(response.state, "pending");
+        assert_eq!(response.amount, 1000);
+        assert_eq!(response.prepay_id.as_deref(), Some("wx_mock_ORDER123_R1"));
+        assert!(response.pay_params.is_some());
+
+        let original = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(original.state, PaymentState::Failed);
+
+        // 重试订单未完成时重复重试返回同一订单，不会产生第二个可支付订单
+        let response = service.retry_payment("ORDER123").await.unwrap();
+        assert_eq!(response.out_order_no, "ORDER123_R1");
+        assert!(response.pay_params.is_some());
+        assert!(
+            service
+                .repository
+                .find_by_out_order_no("ORDER123_R2")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // 重试订单也失败后，再次重试使用下一个后缀
+        service.query_payment("ORDER123_R1").await.unwrap();
+        let response = service.retry_payment("ORDER123").await.unwrap();
+        assert_eq!(response.out_order_no, "ORDER123_R2");
+    }
+
+    #[tokio::test]
+    async fn test_retry_rejects_order_number_without_room_for_suffix() {
+        let (service, wechat_pay) = service();
+        let out_order_no = "A".repeat(limits::MAX_OUT_ORDER_NO_BYTES - 2);
+        service
+            .create_payment(create_request(&out_order_no))
+            .await
+            .unwrap();
+        wechat_pay.set_query_response("CLOSED", None, None);
+        service.query_payment(&out_order_no).await.unwrap();
+
+        let result = service.retry_payment(&out_order_no).await;
+
+        assert!(
+            matches!(&result, Err(DomainError::ValidationError(msg)) if msg.contains("Retry order number")),
+            "{:?}",
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_succeeded_order_rejected() {
+        let (service, wechat_pay) = service();
+        create_succeeded_order(&service, &wechat_pay, "ORDER123").await;
+
+        let result = service.retry_payment("ORDER123").await;
+
+        assert!(matches!(result, Err(DomainError::InvalidState { .. })));
+        assert!(
+            service
+                .repository
+                .find_by_out_order_no("ORDER123_R1")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refund_unpaid_order_rejected() {
+        let (service, _) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+
+        let result = service
+            .refund_payment("ORDER123", Money::from_cents(100), None)
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidState { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_merchant_id_flows_from_request_to_order_and_event() {
+        let (service, _) = service();
+        let mut request = create_request("ORDER123");
+        request.merchant_id = Some("1900000222".to_string());
+
+        let response = service.create_payment(request).await.unwrap();
+        assert_eq!(response.merchant_id, "1900000222");
+
+        let order = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(order.merchant_id, "1900000222");
+
+        let event = crate::domain::PaymentOrderCreated::from_order(&order);
+        assert_eq!(event.merchant_id, "1900000222");
+    }
+
+    #[tokio::test]
+    async fn test_default_merchant_id_used_when_request_omits_it() {
+        let (service, _) = service();
+        let service = service.with_config(PaymentServiceConfig {
+            default_merchant_id: Some("1900000109".to_string()),
+            ..PaymentServiceConfig::default()
+        });
+        let mut request = create_request("ORDER123");
+        request.merchant_id = None;
+
+        let response = service.create_payment(request).await.unwrap();
+
+        assert_eq!(response.merchant_id, "1900000109");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_merchant_id_rejected() {
+        let (service, _) = service();
+        let service = service.with_config(PaymentServiceConfig {
+            default_merchant_id: Some("1900000109".to_string()),
+            ..PaymentServiceConfig::default()
+        });
+        let mut request = create_request("ORDER123");
+        request.merchant_id = Some("1900000222".to_string());
+
+        let result = service.create_payment(request).await;
+
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+        assert!(
+            service
+                .repository
+                .find_by_out_order_no("ORDER123")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // 与配置的商户号一致时允许下单
+        let mut request = create_request("ORDER123");
+        request.merchant_id = Some("1900000109".to_string());
+        let response = service.create_payment(request).await.unwrap();
+        assert_eq!(response.merchant_id, "1900000109");
+    }
+
+    #[tokio::test]
+    async fn test_create_payment_rejects_amount_below_method_floor() {
+        let (service, _) = service();
+        let service = service.with_config(PaymentServiceConfig {
+            min_amounts: crate::application::MinAmounts {
+                mini_program_cents: 1000,
+                ..Default::default()
+            },
+            ..PaymentServiceConfig::default()
+        });
+
+        let request = CreatePaymentRequest {
+            amount: Money::from_cents(999),
+            ..create_request("ORDER_LOW")
+        };
+        let result = service.create_payment(request).await;
+        assert!(matches!(result, Err(DomainError::InvalidAmount(_))));
+        assert!(
+            service
+                .repository
+                .find_by_out_order_no("ORDER_LOW")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // 恰好等于下限时允许下单
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_missing_merchant_id_rejected() {
+        let (service, _) = service();
+        let mut request = create_request("ORDER123");
+        request.merchant_id = None;
+
+        let result = service.create_payment(request).await;
+
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_payments_scoped_by_merchant() {
+        let (service, _) = service();
+        for (merchant_id, out_order_no) in [("M1", "ORDER1"), ("M2", "ORDER2"), ("M1", "ORDER3")] {
+            let mut request = create_request(out_order_no);
+            request.merchant_id = Some(merchant_id.to_string());
+            service.create_payment(request).await.unwrap();
+        }
+
+        let response = service
+            .list_payments(&OrderListQuery {
+                merchant_id: Some("M1".to_string()),
+                state: None,
+                payment_method: None,
+                created_from: None,
+                created_before: None,
+                page: 1,
+                page_size: 20,
+                sort: crate::ports::OrderSortField::CreatedAt,
+                direction: crate::ports::SortDirection::Asc,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.total, 2);
+        assert!(response.items.iter().all(|item| item.merchant_id == "M1"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_orders_yields_rows_incrementally() {
+        use futures::StreamExt;
+
+        let (service, _) = service();
+        for n in 0..5 {
+            let mut request = create_request(&format!("ORDER{}", n));
+            if n % 2 == 1 {
+                request.merchant_id = Some("OTHER".to_string());
+            }
+            service.create_payment(request).await.unwrap();
+        }
+
+        let mut stream = service.stream_orders(OrderExportFilter::default());
+        let mut count = 0;
+        while let Some(order) = stream.next().await {
+            order.unwrap();
+            count += 1;
+        }
+        assert_eq!(count, 5);
+
+        let filtered = service
+            .stream_orders(OrderExportFilter {
+                merchant_id: Some("OTHER".to_string()),
+                ..Default::default()
+            })
+            .fold(0, |count, order| async move {
+                assert_eq!(order.unwrap().merchant_id, "OTHER");
+                count + 1
+            })
+            .await;
+        assert_eq!(filtered, 2);
+    }
+
+    #[tokio::test]
+    async fn test_close_payment_marks_order_closed() {
+        let (service, _) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+
+        let response = service.close_payment("ORDER123").await.unwrap();
+        assert_eq!(response.state, "closed");
+
+        // 重复关闭是幂等的
+        let response = service.close_payment("ORDER123").await.unwrap();
+        assert_eq!(response.state, "closed");
+    }
+
+    #[tokio::test]
+    async fn test_close_paid_order_reconciles_to_succeeded() {
+        let (service, wechat_pay) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+        wechat_pay.set_close_error(400, r#"{"code":"ORDERPAID","message":"订单已支付"}"#);
+        wechat_pay.set_query_response("SUCCESS", Some("TX123"), None);
+
+        let response = service.close_payment("ORDER123").await.unwrap();
+
+        assert_eq!(response.state, "succeeded");
+        let order = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(order.state, PaymentState::Succeeded);
+        assert_eq!(order.transaction_id.as_deref(), Some("TX123"));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_pending_syncs_finished_orders() {
+        let (service, wechat_pay) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+        wechat_pay.set_query_response("SUCCESS", Some("TX123"), None);
+
+        let report = service
+            .reconcile_pending(
+                Utc::now() + chrono::Duration::seconds(1),
+                chrono::Duration::days(1),
+                100,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.reconciled, 1);
+        let order = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(order.state, PaymentState::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_payment_success_enqueues_outbox_event() {
+        let (service, _) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+
+        let notification = success_notification(serde_json::json!({
+            "out_trade_no": "ORDER123",
+            "transaction_id": "TX123"
+        }));
+        service
+            .handle_payment_notification(notification)
+            .await
+            .unwrap();
+
+        let events = service.repository.outbox_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "PaymentSucceeded");
+        assert_eq!(events[0].aggregate_id, "ORDER123");
+        assert_eq!(events[0].state, crate::domain::OutboxState::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_pending_skips_recent_orders() {
+        let (service, wechat_pay) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+
+        let report = service
+            .reconcile_pending(
+                Utc::now() - chrono::Duration::minutes(5),
+                chrono::Duration::days(1),
+                100,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report, ReconcileReport::default());
+        assert_eq!(wechat_pay.query_calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_partitioned_reconcile_covers_each_window_once() {
+        let (service, wechat_pay) = service();
+        let day_start = Utc::now()
+            .duration_trunc(chrono::Duration::days(1))
+            .unwrap()
+            - chrono::Duration::days(3);
+
+        // 三天的积压订单，每天5笔，分页大小为2，确保跨页与跨窗口
+        let mut expected = Vec::new();
+        for day in 0..3 {
+            for n in 0..5 {
+                let out_order_no = format!("ORDER-D{}-{}", day, n);
+                let mut order = PaymentOrder::new(
+                    "1900000109".to_string(),
+                    out_order_no.clone(),
+                    Money::from_cents(1000),
+                    PaymentMethod::MiniProgram,
+                    "测试商品".to_string(),
+                    "127.0.0.1".to_string(),
+                    Some("openid123".to_string()),
+                    None,
+                )
+                .unwrap();
+                order.created_at =
+                    day_start + chrono::Duration::days(day) + chrono::Duration::hours(n * 4);
+                service.repository.save(&order).await.unwrap();
+                expected.push(out_order_no);
+            }
+        }
+
+        let report = service
+            .reconcile_pending(
+                day_start + chrono::Duration::days(3),
+                chrono::Duration::days(1),
+                2,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.windows, 3);
+        assert_eq!(report.scanned, 15);
+        assert_eq!(report.reconciled, 0);
+        // 仍为未支付的订单不会被重复扫描，也不会遗漏
+        assert_eq!(wechat_pay.queried_orders(), expected);
+    }
+
+    /// 模拟进程在保存订单后、预下单前中断：订单已落库但没有预下单结果
+    async fn seed_interrupted_order(
+        service: &PaymentService<MockWeChatPayAdapter, InMemoryPaymentRepository>,
+        out_order_no: &str,
+        age: chrono::Duration,
+    ) {
+        let mut order = PaymentOrder::new(
+            "1900000109".to_string(),
+            out_order_no.to_string(),
+            Money::from_cents(1000),
+            PaymentMethod::MiniProgram,
+            "测试商品".to_string(),
+            "127.0.0.1".to_string(),
+            Some("openid123".to_string()),
+            None,
+        )
+        .unwrap();
+        order.created_at = Utc::now() - age;
+        service.repository.save(&order).await.unwrap();
+    }
+
+    async fn heal_last_day(
+        service: &PaymentService<MockWeChatPayAdapter, InMemoryPaymentRepository>,
+    ) -> StartupHealReport {
+        let created_before = Utc::now() - chrono::Duration::minutes(5);
+        service
+            .heal_interrupted_orders(
+                created_before - chrono::Duration::days(1),
+                created_before,
+                100,
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_startup_heal_stops_on_shutdown() {
+        let (service, wechat_pay) = service();
+        seed_interrupted_order(&service, "ORDER1", chrono::Duration::hours(2)).await;
+        seed_interrupted_order(&service, "ORDER2", chrono::Duration::hours(1)).await;
+        let shutdown = CancellationToken::new();
+        shutdown.cancel();
+
+        let created_before = Utc::now() - chrono::Duration::minutes(5);
+        let report = service
+            .heal_interrupted_orders(
+                created_before - chrono::Duration::days(1),
+                created_before,
+                100,
+                &shutdown,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.scanned, 2);
+        assert_eq!(report.skipped, 2);
+        assert_eq!(wechat_pay.query_calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_startup_heal_recovers_missing_prepay_id() {
+        let (service, wechat_pay) = service();
+        seed_interrupted_order(&service, "ORDER123", chrono::Duration::hours(1)).await;
+        // 超出回看范围或过新的订单不处理
+        seed_interrupted_order(&service, "ORDER_OLD", chrono::Duration::days(2)).await;
+        seed_interrupted_order(&service, "ORDER_NEW", chrono::Duration::seconds(10)).await;
+
+        let report = heal_last_day(&service).await;
+
+        assert_eq!(
+            report,
+            StartupHealReport {
+                scanned: 1,
+                prepay_recovered: 1,
+                ..StartupHealReport::default()
+            }
+        );
+        assert_eq!(wechat_pay.queried_orders(), vec!["ORDER123".to_string()]);
+        let order = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(order.state, PaymentState::Pending);
+        assert_eq!(order.prepay_id.as_deref(), Some("wx_mock_ORDER123"));
+    }
+
+    #[tokio::test]
+    async fn test_startup_heal_closes_orders_unknown_to_wechat() {
+        let (service, wechat_pay) = service();
+        seed_interrupted_order(&service, "ORDER123", chrono::Duration::hours(1)).await;
+        wechat_pay.set_query_error(404, r#"{"code":"ORDER_NOT_EXIST","message":"订单不存在"}"#);
+
+        let report = heal_last_day(&service).await;
+
+        assert_eq!(report.closed, 1);
+        assert_eq!(report.failed, 0);
+        let order = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(order.state, PaymentState::Closed);
+        assert_eq!(order.prepay_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_startup_heal_syncs_paid_orders() {
+        let (service, wechat_pay) = service();
+        seed_interrupted_order(&service, "ORDER123", chrono::Duration::hours(1)).await;
+        wechat_pay.set_query_response("SUCCESS", Some("TX123"), None);
+
+        let report = heal_last_day(&service).await;
+
+        assert_eq!(report.reconciled, 1);
+        assert_eq!(report.prepay_recovered, 0);
+        let order = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(order.state, PaymentState::Succeeded);
+        assert_eq!(order.transaction_id.as_deref(), Some("TX123"));
+    }
+
+    #[tokio::test]
+    async fn test_startup_heal_counts_failures() {
+        let (service, wechat_pay) = service();
+        seed_interrupted_order(&service, "ORDER123", chrono::Duration::hours(1)).await;
+        wechat_pay.set_query_error(500, r#"{"code":"SYSTEM_ERROR","message":"系统错误"}"#);
+
+        let report = heal_last_day(&service).await;
+
+        assert_eq!(report.scanned, 1);
+        assert_eq!(report.failed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_rejects_empty_window() {
+        let (service, _) = service();
+
+        let result = service
+            .reconcile_pending(Utc::now(), chrono::Duration::zero(), 100)
+            .await;
+
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_persisted_webhook_payload_is_redacted() {
+        let (service, _) = service();
+        let service = service.with_config(PaymentServiceConfig {
+            persist_webhook_payloads: true,
+            ..PaymentServiceConfig::default()
+        });
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+
+        let notification = success_notification(serde_json::json!({
+            "out_trade_no": "ORDER123",
+            "transaction_id": "TX123",
+            "trade_state": "SUCCESS",
+            "bank_type": "CMC",
+            "payer": { "openid": "oUpF8uMuAJO_M2pxb1Q9zNjWeS6o" },
+            "amount": { "total": 1000, "payer_total": 1000 }
+        }));
+        service
+            .handle_payment_notification(notification)
+            .await
+            .unwrap();
+
+        let events = service.repository.webhook_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].out_order_no.as_deref(), Some("ORDER123"));
+
+        let payload: serde_json::Value = serde_json::from_str(&events[0].payload).unwrap();
+        assert_eq!(payload["transaction_id"], "TX123");
+        assert_eq!(payload["amount"]["total"], 1000);
+        assert_eq!(payload["payer"]["openid"], "oUp***S6o");
+        assert_eq!(payload["bank_type"], "***");
+        assert!(!events[0].payload.contains("oUpF8uMuAJO_M2pxb1Q9zNjWeS6o"));
+    }
+
+    fn openid_checking_service() -> PaymentService<MockWeChatPayAdapter, InMemoryPaymentRepository>
+    {
+        service().0.with_config(PaymentServiceConfig {
+            require_payer_openid_match: true,
+            ..PaymentServiceConfig::default()
+        })
+    }
+
+    async fn order_state(
+        service: &PaymentService<MockWeChatPayAdapter, InMemoryPaymentRepository>,
+        out_order_no: &str,
+    ) -> PaymentState {
+        service
+            .repository
+            .find_by_out_order_no(out_order_no)
+            .await
+            .unwrap()
+            .unwrap()
+            .state
+    }
+
+    #[tokio::test]
+    async fn test_payer_openid_match_marks_succeeded() {
+        let service = openid_checking_service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+
+        let notification = success_notification(serde_json::json!({
+            "out_trade_no": "ORDER123",
+            "transaction_id": "TX123",
+            "payer": { "openid": "openid123" }
+        }));
+        service
+            .handle_payment_notification(notification)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            order_state(&service, "ORDER123").await,
+            PaymentState::Succeeded
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payer_openid_mismatch_rejected() {
+        let service = openid_checking_service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+
+        for payer in [
+            serde_json::json!({ "openid": "someone_else" }),
+            serde_json::json!({}),
+        ] {
+            let notification = success_notification(serde_json::json!({
+                "out_trade_no": "ORDER123",
+                "transaction_id": "TX123",
+                "payer": payer
+            }));
+            let result = service.handle_payment_notification(notification).await;
+            assert!(matches!(result, Err(DomainError::ValidationError(_))));
+        }
+
+        assert_eq!(
+            order_state(&service, "ORDER123").await,
+            PaymentState::Pending
+        );
+        assert!(service.repository.outbox_events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_payer_openid_not_checked_for_openid_less_orders() {
+        let service = openid_checking_service();
+        let mut request = create_request("ORDER123");
+        request.payment_method = PaymentMethod::Native;
+        request.openid = None;
+        service.create_payment(request).await.unwrap();
+
+        let notification = success_notification(serde_json::json!({
+            "out_trade_no": "ORDER123",
+            "transaction_id": "TX123",
+            "payer": { "openid": "scanner_openid" }
+        }));
+        service
+            .handle_payment_notification(notification)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            order_state(&service, "ORDER123").await,
+            PaymentState::Succeeded
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhook_payload_not_persisted_by_default() {
+        let (service, _) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+
+        let notification = success_notification(serde_json::json!({
+            "out_trade_no": "ORDER123",
+            "transaction_id": "TX123"
+        }));
+        service
+            .handle_payment_notification(notification)
+            .await
+            .unwrap();
+
+        assert!(service.repository.webhook_events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unit_of_work_failure_rolls_back_all_writes() {
+        let (service, wechat_pay) = service();
+        create_succeeded_order(&service, &wechat_pay, "ORDER123").await;
+        let mut order = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+        let refund = RefundRecord::new(&order, "RF123".to_string(), order.amount, None).unwrap();
+        order.mark_as_refunded().unwrap();
+
+        let result: DomainResult<()> = service
+            .unit_of_work(move |uow| {
+                Box::pin(async move {
+                    uow.save_refund(&refund).await?;
+                    uow.update_state(&order).await?;
+                    Err(DomainError::WeChatPayError("boom".to_string()))
+                })
+            })
+            .await;
+        assert!(result.is_err());
+
+        let stored = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.state, PaymentState::Succeeded);
+        assert!(
+            service
+                .repository
+                .find_refunds_by_order(stored.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_success_notification_is_acknowledged() {
+        let (service, _) = service();
+        service
+            .create_payment(create_request("ORDER123"))
+            .await
+            .unwrap();
+        let notification = || {
+            success_notification(serde_json::json!({
+                "out_trade_no": "ORDER123",
+                "transaction_id": "TX123"
+            }))
+        };
+
+        service
+            .process_notification(notification(), "raw body")
+            .await
+            .unwrap();
+        service
+            .process_notification(notification(), "raw body")
+            .await
+            .unwrap();
+
+        assert!(service.list_dead_letters(10).await.unwrap().is_empty());
+        assert_eq!(service.repository.outbox_events().len(), 1);
+        let order = service
+            .repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(order.state, PaymentState::Succeeded);
+        assert_eq!(order.transaction_id.as_deref(), Some("TX123"));
+
+        // 同一订单出现不同的交易号需要人工核查
+        let conflicting = success_notification(serde_json::json!({
+            "out_trade_no": "ORDER123",
+            "transaction_id": "TX999"
+        }));
+        service
+            .process_notification(conflicting, "raw body")
+            .await
+            .unwrap();
+        let letters = service.list_dead_letters(10).await.unwrap();
+        assert_eq!(letters.len(), 1);
+        assert!(letters[0].reason.contains("TX999"));
+    }
+
+    #[tokio::test]
+    async fn test_unprocessable_notification_moves_to_dead_letter() {
+        let (service, _) = service();
+        let notification = success_notification(serde_json::json!({
+            "out_trade_no": "UNKNOWN_ORDER",
+            "transaction_id": "TX123"
+        }));
+
+        service
+            .process_notification(notification, "raw body")
+            .await
+            .unwrap();
+
+        let letters = service.list_dead_letters(10).await.unwrap();
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].notification_id, "EV-2018022511223320873");
+        assert_eq!(letters[0].raw_body, "raw body");
+        assert!(letters[0].reason.contains("UNKNOWN_ORDER"));
+    }
+}
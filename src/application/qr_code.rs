@@ -0,0 +1,38 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use image::{ImageFormat, Luma};
+use qrcode::QrCode;
+use std::io::Cursor;
+
+/// 二维码图片的最小边长（像素）
+const MIN_QR_CODE_SIZE: u32 = 256;
+
+/// 将内容（如 Native 支付的 code_url）渲染为 PNG 二维码
+pub fn render_png(content: &str) -> DomainResult<Vec<u8>> {
+    let code = QrCode::new(content.as_bytes())
+        .map_err(|e| DomainError::InternalError(format!("QR code encode error: {}", e)))?;
+    let image = code
+        .render::<Luma<u8>>()
+        .min_dimensions(MIN_QR_CODE_SIZE, MIN_QR_CODE_SIZE)
+        .build();
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| DomainError::InternalError(format!("QR code render error: {}", e)))?;
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_png() {
+        let png = render_png("weixin://wxpay/bizpayurl?pr=abc123").unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let image = image::load_from_memory_with_format(&png, ImageFormat::Png).unwrap();
+        assert!(image.width() >= MIN_QR_CODE_SIZE);
+        assert_eq!(image.width(), image.height());
+    }
+}
@@ -0,0 +1,99 @@
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// 需要脱敏的字段（用户标识与银行信息）
+const SENSITIVE_KEYS: &[&str] = &[
+    "openid",
+    "sp_openid",
+    "sub_openid",
+    "bank_type",
+    "bank_name",
+    "user_received_account",
+    "payer_client_ip",
+];
+
+/// 对字符串做掩码：保留首尾各3个字符，过短则全部隐藏
+pub fn mask(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() <= 8 {
+        return "***".to_string();
+    }
+
+    let head: String = chars[..3].iter().collect();
+    let tail: String = chars[chars.len() - 3..].iter().collect();
+    format!("{}***{}", head, tail)
+}
+
+/// 递归地对JSON中的敏感字段做脱敏
+pub fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if SENSITIVE_KEYS.contains(&key.as_str()) {
+                    if let Value::String(text) = field {
+                        *text = mask(text);
+                    } else if !field.is_null() {
+                        *field = Value::String("***".to_string());
+                    }
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+/// 返回脱敏后的JSON文本
+pub fn redacted(value: &Value) -> String {
+    let mut copy = value.clone();
+    redact_json(&mut copy);
+    copy.to_string()
+}
+
+/// 密钥指纹（SHA-256 前8字节），用于审计日志中标识密钥而不泄露密钥本身
+pub fn fingerprint(secret: &str) -> String {
+    hex::encode(&Sha256::digest(secret.as_bytes())[..8])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(mask("oUpF8uMuAJO_M2pxb1Q9zNjWeS6o"), "oUp***S6o");
+        assert_eq!(mask("CMC"), "***");
+    }
+
+    #[test]
+    fn test_redact_json_masks_nested_fields() {
+        let mut value = json!({
+            "out_trade_no": "ORDER123",
+            "bank_type": "CMC",
+            "payer": { "openid": "oUpF8uMuAJO_M2pxb1Q9zNjWeS6o" },
+            "promotion_detail": [{ "sub_openid": "oUpF8uMuAJO_M2pxb1Q9zNjWeS6o" }]
+        });
+
+        redact_json(&mut value);
+
+        assert_eq!(value["out_trade_no"], "ORDER123");
+        assert_eq!(value["bank_type"], "***");
+        assert_eq!(value["payer"]["openid"], "oUp***S6o");
+        assert_eq!(value["promotion_detail"][0]["sub_openid"], "oUp***S6o");
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable_and_hides_secret() {
+        let key = "0123456789abcdef0123456789abcdef";
+        assert_eq!(fingerprint(key), fingerprint(key));
+        assert_eq!(fingerprint(key).len(), 16);
+        assert_ne!(
+            fingerprint(key),
+            fingerprint("fedcba9876543210fedcba9876543210")
+        );
+        assert!(!fingerprint(key).contains("0123456789"));
+    }
+}
@@ -0,0 +1,290 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::value_objects::{Money, PaymentMethod, RoundingMode};
+
+/// 支付服务配置
+#[derive(Debug, Clone)]
+pub struct PaymentServiceConfig {
+    /// 测试环境大额保护（为 None 时不启用，生产环境不应设置）
+    pub amount_guard: Option<AmountGuard>,
+
+    /// 单笔订单最多退款次数（微信支付限制单笔订单最多50次部分退款）
+    pub max_refunds_per_order: usize,
+
+    /// 已配置的商户号（即 WECHAT_MCHID）：请求未指定商户时使用，指定其他商户号时拒绝下单
+    pub default_merchant_id: Option<String>,
+
+    /// 是否保存脱敏后的回调解密报文（用于对账审计，默认关闭）
+    pub persist_webhook_payloads: bool,
+
+    /// 支付成功通知的付款人 openid 必须与订单 openid 一致（订单无 openid 时不校验，默认关闭）
+    pub require_payer_openid_match: bool,
+
+    /// 各支付方式的最低下单金额
+    pub min_amounts: MinAmounts,
+
+    /// 统计报表中金额展示的舍入方式
+    pub report_rounding: RoundingMode,
+
+    /// 统计报表中金额展示的小数位数（0-2）
+    pub report_scale: u32,
+}
+
+impl Default for PaymentServiceConfig {
+    fn default() -> Self {
+        Self {
+            amount_guard: None,
+            max_refunds_per_order: 50,
+            default_merchant_id: None,
+            persist_webhook_payloads: false,
+            require_payer_openid_match: false,
+            min_amounts: MinAmounts::default(),
+            report_rounding: RoundingMode::HalfUp,
+            report_scale: 2,
+        }
+    }
+}
+
+impl PaymentServiceConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            amount_guard: None,
+            max_refunds_per_order: std::env::var("MAX_REFUNDS_PER_ORDER")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.max_refunds_per_order),
+            default_merchant_id: None,
+            persist_webhook_payloads: std::env::var("PERSIST_WEBHOOK_PAYLOADS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.persist_webhook_payloads),
+            require_payer_openid_match: std::env::var("REQUIRE_PAYER_OPENID_MATCH")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.require_payer_openid_match),
+            min_amounts: MinAmounts::from_env(),
+            report_rounding: std::env::var("REPORT_ROUNDING_MODE")
+                .ok()
+                .and_then(|v| RoundingMode::parse(&v))
+                .unwrap_or(default.report_rounding),
+            report_scale: std::env::var("REPORT_AMOUNT_SCALE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|scale| *scale <= 2)
+                .unwrap_or(default.report_scale),
+        }
+    }
+}
+
+/// 各支付方式的最低下单金额（分）
+///
+/// 微信支付的最低金额为1分，商户可为特定支付方式设置更高的下限（如不允许1元以下的扫码支付）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinAmounts {
+    pub mini_program_cents: i64,
+    pub jsapi_cents: i64,
+    pub native_cents: i64,
+    pub h5_cents: i64,
+}
+
+impl Default for MinAmounts {
+    fn default() -> Self {
+        Self {
+            mini_program_cents: 1,
+            jsapi_cents: 1,
+            native_cents: 1,
+            h5_cents: 1,
+        }
+    }
+}
+
+impl MinAmounts {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let cents = |name: &str, fallback: i64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(fallback)
+        };
+        Self {
+            mini_program_cents: cents("MIN_AMOUNT_CENTS_MINI_PROGRAM", default.mini_program_cents),
+            jsapi_cents: cents("MIN_AMOUNT_CENTS_JSAPI", default.jsapi_cents),
+            native_cents: cents("MIN_AMOUNT_CENTS_NATIVE", default.native_cents),
+            h5_cents: cents("MIN_AMOUNT_CENTS_H5", default.h5_cents),
+        }
+    }
+
+    /// 指定支付方式的最低金额（分）
+    pub fn for_method(&self, method: PaymentMethod) -> i64 {
+        match method {
+            PaymentMethod::MiniProgram => self.mini_program_cents,
+            PaymentMethod::Jsapi => self.jsapi_cents,
+            PaymentMethod::Native => self.native_cents,
+            PaymentMethod::H5 => self.h5_cents,
+        }
+    }
+
+    /// 检查金额是否达到支付方式的最低金额
+    pub fn check(&self, method: PaymentMethod, amount: Money) -> DomainResult<()> {
+        let floor = self.for_method(method);
+        if amount.to_cents() < floor {
+            return Err(DomainError::InvalidAmount(format!(
+                "{} is below the minimum {} for {} payments",
+                amount,
+                Money::from_cents(floor),
+                method
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 测试环境大额保护
+///
+/// 防止测试/预发布环境误发起大额真实支付：超过上限或疑似"整数"大额的金额
+/// 必须在请求中显式设置 `confirm_large_amount` 才能放行。
+#[derive(Debug, Clone)]
+pub struct AmountGuard {
+    /// 金额上限（分）
+    pub max_amount_cents: i64,
+
+    /// 是否拦截可疑的整百元金额
+    pub block_round_amounts: bool,
+}
+
+impl Default for AmountGuard {
+    fn default() -> Self {
+        Self {
+            max_amount_cents: 10_000,
+            block_round_amounts: true,
+        }
+    }
+}
+
+impl AmountGuard {
+    /// 整百元（分）
+    const ROUND_UNIT_CENTS: i64 = 10_000;
+
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            max_amount_cents: std::env::var("TEST_AMOUNT_GUARD_MAX_CENTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.max_amount_cents),
+            block_round_amounts: std::env::var("TEST_AMOUNT_GUARD_BLOCK_ROUND")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.block_round_amounts),
+        }
+    }
+
+    /// 检查金额，`confirmed` 为请求中的显式放行标记
+    pub fn check(&self, amount: Money, confirmed: bool) -> DomainResult<()> {
+        if confirmed {
+            return Ok(());
+        }
+
+        let cents = amount.to_cents();
+        if cents > self.max_amount_cents {
+            return Err(DomainError::InvalidAmount(format!(
+                "{} exceeds the test environment limit {}; set confirm_large_amount=true to proceed",
+                amount,
+                Money::from_cents(self.max_amount_cents)
+            )));
+        }
+
+        if self.block_round_amounts
+            && cents >= Self::ROUND_UNIT_CENTS
+            && cents % Self::ROUND_UNIT_CENTS == 0
+        {
+            return Err(DomainError::InvalidAmount(format!(
+                "{} looks like a suspicious round amount in a test environment; set confirm_large_amount=true to proceed",
+                amount
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_amount_enforced_per_method() {
+        let min_amounts = MinAmounts {
+            native_cents: 100,
+            ..MinAmounts::default()
+        };
+
+        match min_amounts.check(PaymentMethod::Native, Money::from_cents(99)) {
+            Err(DomainError::InvalidAmount(message)) => {
+                assert!(message.contains("native"));
+                assert!(message.contains(&Money::from_cents(100).to_string()));
+            }
+            other => panic!("expected InvalidAmount, got {:?}", other),
+        }
+        assert!(
+            min_amounts
+                .check(PaymentMethod::Native, Money::from_cents(100))
+                .is_ok()
+        );
+
+        // 其他支付方式仍使用默认下限
+        assert!(
+            min_amounts
+                .check(PaymentMethod::MiniProgram, Money::from_cents(1))
+                .is_ok()
+        );
+        assert!(
+            min_amounts
+                .check(PaymentMethod::H5, Money::from_cents(0))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_amount_guard_allows_under_threshold() {
+        let guard = AmountGuard::default();
+        assert!(guard.check(Money::from_cents(1), false).is_ok());
+        assert!(guard.check(Money::from_cents(9_999), false).is_ok());
+    }
+
+    #[test]
+    fn test_amount_guard_blocks_over_threshold_without_override() {
+        let guard = AmountGuard::default();
+        let result = guard.check(Money::from_cents(10_001), false);
+        match result {
+            Err(DomainError::InvalidAmount(message)) => {
+                assert!(message.contains("confirm_large_amount"))
+            }
+            other => panic!("expected InvalidAmount, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_amount_guard_allows_over_threshold_with_override() {
+        let guard = AmountGuard::default();
+        assert!(guard.check(Money::from_yuan(5_000), true).is_ok());
+    }
+
+    #[test]
+    fn test_amount_guard_blocks_round_amounts() {
+        let guard = AmountGuard {
+            max_amount_cents: 1_000_000,
+            block_round_amounts: true,
+        };
+        assert!(guard.check(Money::from_yuan(500), false).is_err());
+        assert!(guard.check(Money::from_cents(50_001), false).is_ok());
+        assert!(guard.check(Money::from_yuan(500), true).is_ok());
+
+        let lenient = AmountGuard {
+            block_round_amounts: false,
+            ..guard
+        };
+        assert!(lenient.check(Money::from_yuan(500), false).is_ok());
+    }
+}
@@ -1,5 +1,9 @@
 use crate::domain::errors::{DomainError, DomainResult};
-use crate::domain::value_objects::{Money, PaymentMethod, PaymentState};
+use crate::domain::events::DomainEvent;
+use crate::domain::limits::{self, check_optional, check_required};
+use crate::domain::value_objects::{
+    Money, OutboxState, PaymentMethod, PaymentState, RefundDenial, RefundState, Refundability,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -10,6 +14,9 @@ pub struct PaymentOrder {
     /// 订单ID（内部）
     pub id: Uuid,
 
+    /// 所属商户号
+    pub merchant_id: String,
+
     /// 商户订单号
     pub out_order_no: String,
 
@@ -48,11 +55,22 @@ pub struct PaymentOrder {
 
     /// 微信支付预下单ID
     pub prepay_id: Option<String>,
+
+    /// 重新发起支付时，被重试的原商户订单号
+    pub parent_out_order_no: Option<String>,
+
+    /// 订单优惠标记（代金券活动批次）
+    pub goods_tag: Option<String>,
+
+    /// Native 支付的二维码链接
+    pub code_url: Option<String>,
 }
 
 impl PaymentOrder {
     /// 创建新的支付订单
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
+        merchant_id: String,
         out_order_no: String,
         amount: Money,
         payment_method: PaymentMethod,
@@ -68,24 +86,27 @@ impl PaymentOrder {
             ));
         }
 
-        // 验证商户订单号
-        if out_order_no.is_empty() || out_order_no.len() > 64 {
-            return Err(DomainError::ValidationError(
-                "Out order no must be 1-64 characters".to_string(),
-            ));
-        }
-
-        // 验证描述
-        if description.is_empty() || description.len() > 127 {
-            return Err(DomainError::ValidationError(
-                "Description must be 1-127 characters".to_string(),
-            ));
-        }
+        // 去除首尾空白，纯空白值在微信侧会被拒绝，这里提前拦截
+        let out_order_no = not_blank("Out order no", out_order_no)?;
+        let description = not_blank("Description", description)?;
+
+        // 验证字段长度（按字节计算，与微信支付限制一致）
+        check_required("Merchant id", &merchant_id, limits::MAX_MERCHANT_ID_BYTES)?;
+        check_required(
+            "Out order no",
+            &out_order_no,
+            limits::MAX_OUT_ORDER_NO_BYTES,
+        )?;
+        check_required("Description", &description, limits::MAX_DESCRIPTION_BYTES)?;
+        check_optional("Attach", attach.as_deref(), limits::MAX_ATTACH_BYTES)?;
+        check_optional("Openid", openid.as_deref(), limits::MAX_OPENID_BYTES)?;
+        check_required("Client ip", &client_ip, limits::MAX_CLIENT_IP_BYTES)?;
 
         let now = Utc::now();
 
         Ok(Self {
             id: Uuid::new_v4(),
+            merchant_id,
             out_order_no,
             transaction_id: None,
             amount,
@@ -99,9 +120,64 @@ impl PaymentOrder {
             paid_at: None,
             attach,
             prepay_id: None,
+            parent_out_order_no: None,
+            goods_tag: None,
+            code_url: None,
         })
     }
 
+    /// 设置订单优惠标记：1-32 字节，仅允许字母、数字、`_` 和 `-`
+    pub fn with_goods_tag(mut self, goods_tag: Option<String>) -> DomainResult<Self> {
+        if let Some(tag) = &goods_tag {
+            check_required("Goods tag", tag, limits::MAX_GOODS_TAG_BYTES)?;
+            if !tag
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+            {
+                return Err(DomainError::ValidationError(format!(
+                    "Goods tag contains invalid characters: {}",
+                    tag
+                )));
+            }
+        }
+
+        self.goods_tag = goods_tag;
+        Ok(self)
+    }
+
+    /// 为失败或已关闭的订单创建新的支付尝试（新的商户订单号，原订单保持终态）
+    pub fn retry_of(parent: &PaymentOrder, out_order_no: String) -> DomainResult<Self> {
+        if !matches!(parent.state, PaymentState::Failed | PaymentState::Closed) {
+            return Err(DomainError::InvalidState {
+                expected: "failed or closed".to_string(),
+                actual: parent.state.to_string(),
+            });
+        }
+
+        if out_order_no.len() > limits::MAX_OUT_ORDER_NO_BYTES {
+            return Err(DomainError::ValidationError(format!(
+                "Retry order number {} exceeds {} bytes, order {} cannot be retried",
+                out_order_no,
+                limits::MAX_OUT_ORDER_NO_BYTES,
+                parent.out_order_no
+            )));
+        }
+
+        let mut order = Self::new(
+            parent.merchant_id.clone(),
+            out_order_no,
+            parent.amount,
+            parent.payment_method,
+            parent.description.clone(),
+            parent.client_ip.clone(),
+            parent.openid.clone(),
+            parent.attach.clone(),
+        )?
+        .with_goods_tag(parent.goods_tag.clone())?;
+        order.parent_out_order_no = Some(parent.out_order_no.clone());
+        Ok(order)
+    }
+
     /// 更新为处理中状态
     pub fn mark_as_processing(&mut self) -> DomainResult<()> {
         if self.state != PaymentState::Pending {
@@ -160,6 +236,20 @@ impl PaymentOrder {
         Ok(())
     }
 
+    /// 标记为已退款（全额退款后）
+    pub fn mark_as_refunded(&mut self) -> DomainResult<()> {
+        if self.state != PaymentState::Succeeded {
+            return Err(DomainError::InvalidState {
+                expected: PaymentState::Succeeded.to_string(),
+                actual: self.state.to_string(),
+            });
+        }
+
+        self.state = PaymentState::Refunded;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
     /// 设置预下单ID
     pub fn set_prepay_id(&mut self, prepay_id: String) -> DomainResult<()> {
         self.prepay_id = Some(prepay_id);
@@ -167,20 +257,350 @@ impl PaymentOrder {
         Ok(())
     }
 
+    /// 设置 Native 支付二维码链接
+    pub fn set_code_url(&mut self, code_url: String) -> DomainResult<()> {
+        self.code_url = Some(code_url);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// 计算订单当前是否可以退款，`already_refunded` 为已生效的退款总额
+    pub fn refundability(&self, already_refunded: Money) -> Refundability {
+        self.refundability_at(already_refunded, Utc::now())
+    }
+
+    /// 以 `now` 为当前时间计算可退款情况
+    pub fn refundability_at(&self, already_refunded: Money, now: DateTime<Utc>) -> Refundability {
+        let denied = |reason| Refundability::Denied { reason };
+        match self.state {
+            PaymentState::Succeeded => {}
+            PaymentState::Refunded => return denied(RefundDenial::FullyRefunded),
+            _ => return denied(RefundDenial::NotSucceeded),
+        }
+
+        let paid_at = self.paid_at.unwrap_or(self.created_at);
+        if now - paid_at > chrono::Duration::days(limits::REFUND_WINDOW_DAYS) {
+            return denied(RefundDenial::TooOld);
+        }
+
+        let remaining = self.amount.to_cents() - already_refunded.to_cents();
+        if remaining <= 0 {
+            return denied(RefundDenial::FullyRefunded);
+        }
+
+        Refundability::Allowed {
+            max: Money::from_cents(remaining),
+        }
+    }
+
     /// 检查是否可以支付
     pub fn can_pay(&self) -> bool {
         self.state == PaymentState::Pending
     }
 
-    /// 检查是否已完成（成功或失败）
+    /// 检查是否已完成（成功、失败、关闭或已退款）
     pub fn is_finished(&self) -> bool {
         matches!(
             self.state,
-            PaymentState::Succeeded | PaymentState::Failed | PaymentState::Closed
+            PaymentState::Succeeded
+                | PaymentState::Failed
+                | PaymentState::Closed
+                | PaymentState::Refunded
         )
     }
 }
 
+/// 退款记录实体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefundRecord {
+    /// 退款ID（内部）
+    pub id: Uuid,
+
+    /// 关联的支付订单ID
+    pub order_id: Uuid,
+
+    /// 商户订单号
+    pub out_order_no: String,
+
+    /// 商户退款单号
+    pub out_refund_no: String,
+
+    /// 微信退款单号
+    pub refund_id: Option<String>,
+
+    /// 退款金额
+    pub amount: Money,
+
+    /// 退款原因
+    pub reason: Option<String>,
+
+    /// 退款状态
+    pub state: RefundState,
+
+    /// 创建时间
+    pub created_at: DateTime<Utc>,
+
+    /// 更新时间
+    pub updated_at: DateTime<Utc>,
+}
+
+impl RefundRecord {
+    /// 为订单创建新的退款记录
+    pub fn new(
+        order: &PaymentOrder,
+        out_refund_no: String,
+        amount: Money,
+        reason: Option<String>,
+    ) -> DomainResult<Self> {
+        if amount.to_cents() <= 0 {
+            return Err(DomainError::InvalidAmount(
+                "Refund amount must be greater than 0".to_string(),
+            ));
+        }
+
+        check_required(
+            "Out refund no",
+            &out_refund_no,
+            limits::MAX_OUT_REFUND_NO_BYTES,
+        )?;
+        check_optional("Reason", reason.as_deref(), limits::MAX_REFUND_REASON_BYTES)?;
+
+        let now = Utc::now();
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            order_id: order.id,
+            out_order_no: order.out_order_no.clone(),
+            out_refund_no,
+            refund_id: None,
+            amount,
+            reason,
+            state: RefundState::Processing,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// 更新微信返回的退款结果
+    pub fn apply_result(&mut self, refund_id: String, state: RefundState) {
+        self.refund_id = Some(refund_id);
+        self.state = state;
+        self.updated_at = Utc::now();
+    }
+
+    /// 微信拒绝受理退款申请，退款关闭且不再占用可退金额
+    pub fn mark_rejected(&mut self) {
+        self.state = RefundState::Closed;
+        self.updated_at = Utc::now();
+    }
+
+    /// 是否占用可退金额（处理中或已成功）
+    pub fn is_effective(&self) -> bool {
+        matches!(self.state, RefundState::Processing | RefundState::Succeeded)
+    }
+}
+
+/// 回调通知审计记录（保存脱敏后的解密报文）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    /// 记录ID
+    pub id: Uuid,
+
+    /// 微信通知ID
+    pub notification_id: String,
+
+    /// 通知类型
+    pub event_type: String,
+
+    /// 商户订单号
+    pub out_order_no: Option<String>,
+
+    /// 脱敏后的解密报文（JSON）
+    pub payload: String,
+
+    /// 接收时间
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookEvent {
+    pub fn new(
+        notification_id: String,
+        event_type: String,
+        out_order_no: Option<String>,
+        payload: String,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            notification_id,
+            event_type,
+            out_order_no,
+            payload,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// 死信通知（无法处理的回调通知，保存原始报文供人工排查）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterNotification {
+    /// 记录ID
+    pub id: Uuid,
+
+    /// 微信通知ID
+    pub notification_id: String,
+
+    /// 通知类型
+    pub event_type: String,
+
+    /// 失败原因
+    pub reason: String,
+
+    /// 原始请求体
+    pub raw_body: String,
+
+    /// 记录时间
+    pub created_at: DateTime<Utc>,
+}
+
+impl DeadLetterNotification {
+    pub fn new(
+        notification_id: String,
+        event_type: String,
+        reason: String,
+        raw_body: String,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            notification_id,
+            event_type,
+            reason,
+            raw_body,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// 发件箱事件（与业务数据在同一事务中写入，由后台任务投递）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxEvent {
+    /// 事件ID
+    pub id: Uuid,
+
+    /// 事件类型
+    pub event_type: String,
+
+    /// 聚合ID（商户订单号）
+    pub aggregate_id: String,
+
+    /// 事件内容（JSON）
+    pub payload: String,
+
+    /// 投递状态
+    pub state: OutboxState,
+
+    /// 已尝试投递次数
+    pub attempts: u32,
+
+    /// 下次投递时间
+    pub next_attempt_at: DateTime<Utc>,
+
+    /// 最近一次投递失败原因
+    pub last_error: Option<String>,
+
+    /// 创建时间
+    pub created_at: DateTime<Utc>,
+
+    /// 投递成功时间
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+impl OutboxEvent {
+    pub fn new<E: DomainEvent + Serialize>(aggregate_id: &str, event: &E) -> DomainResult<Self> {
+        let now = Utc::now();
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            event_type: event.event_type().to_string(),
+            aggregate_id: aggregate_id.to_string(),
+            payload: serde_json::to_string(event)?,
+            state: OutboxState::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+            created_at: now,
+            published_at: None,
+        })
+    }
+
+    /// 标记为已投递
+    pub fn mark_published(&mut self, now: DateTime<Utc>) {
+        self.attempts += 1;
+        self.state = OutboxState::Published;
+        self.published_at = Some(now);
+        self.last_error = None;
+    }
+
+    /// 记录投递失败，达到最大次数后转为 poison 状态
+    pub fn record_failure(&mut self, error: String, retry_at: DateTime<Utc>, max_attempts: u32) {
+        self.attempts += 1;
+        self.last_error = Some(error);
+        if self.attempts >= max_attempts {
+            self.state = OutboxState::Poison;
+        } else {
+            self.next_attempt_at = retry_at;
+        }
+    }
+}
+
+/// 订单状态变更记录（与订单状态在同一事务中写入，用于审计）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateTransition {
+    /// 记录ID
+    pub id: Uuid,
+
+    /// 订单ID
+    pub order_id: Uuid,
+
+    /// 变更前状态
+    pub from_state: PaymentState,
+
+    /// 变更后状态
+    pub to_state: PaymentState,
+
+    /// 变更时间
+    pub created_at: DateTime<Utc>,
+}
+
+impl StateTransition {
+    /// 记录订单从 `from_state` 变更到当前状态
+    pub fn new(order: &PaymentOrder, from_state: PaymentState) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            order_id: order.id,
+            from_state,
+            to_state: order.state,
+            created_at: order.updated_at,
+        }
+    }
+}
+
+/// 去除首尾空白，空值或纯空白值返回校验错误
+fn not_blank(field: &str, value: String) -> DomainResult<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(DomainError::ValidationError(format!(
+            "{} must not be blank",
+            field
+        )));
+    }
+
+    if trimmed.len() == value.len() {
+        Ok(value)
+    } else {
+        Ok(trimmed.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -188,6 +608,7 @@ mod tests {
     #[test]
     fn test_create_payment_order() {
         let order = PaymentOrder::new(
+            "1900000109".to_string(),
             "ORDER123".to_string(),
             Money::from_yuan(10),
             PaymentMethod::MiniProgram,
@@ -207,6 +628,7 @@ mod tests {
     #[test]
     fn test_mark_as_succeeded() {
         let mut order = PaymentOrder::new(
+            "1900000109".to_string(),
             "ORDER123".to_string(),
             Money::from_yuan(10),
             PaymentMethod::MiniProgram,
@@ -225,9 +647,123 @@ mod tests {
         assert!(order.is_finished());
     }
 
+    #[test]
+    fn test_mark_as_refunded_requires_succeeded() {
+        let mut order = PaymentOrder::new(
+            "1900000109".to_string(),
+            "ORDER123".to_string(),
+            Money::from_yuan(10),
+            PaymentMethod::MiniProgram,
+            "测试商品".to_string(),
+            "127.0.0.1".to_string(),
+            Some("openid123".to_string()),
+            None,
+        )
+        .unwrap();
+
+        assert!(order.mark_as_refunded().is_err());
+
+        order.mark_as_succeeded("TX123".to_string()).unwrap();
+        order.mark_as_refunded().unwrap();
+
+        assert_eq!(order.state, PaymentState::Refunded);
+        assert!(order.is_finished());
+    }
+
+    fn succeeded_order() -> PaymentOrder {
+        let mut order = order_with("ORDER123", "测试商品", None).unwrap();
+        order.mark_as_succeeded("TX123".to_string()).unwrap();
+        order
+    }
+
+    #[test]
+    fn test_refundability_allowed_with_remaining_amount() {
+        let order = succeeded_order();
+
+        assert_eq!(
+            order.refundability(Money::from_cents(0)),
+            Refundability::Allowed {
+                max: Money::from_cents(1000)
+            }
+        );
+        assert_eq!(
+            order.refundability(Money::from_cents(300)),
+            Refundability::Allowed {
+                max: Money::from_cents(700)
+            }
+        );
+    }
+
+    #[test]
+    fn test_refundability_denied_when_not_succeeded() {
+        let order = order_with("ORDER123", "测试商品", None).unwrap();
+
+        assert_eq!(
+            order.refundability(Money::from_cents(0)),
+            Refundability::Denied {
+                reason: RefundDenial::NotSucceeded
+            }
+        );
+    }
+
+    #[test]
+    fn test_refundability_denied_when_fully_refunded() {
+        let mut order = succeeded_order();
+        let fully_refunded = Refundability::Denied {
+            reason: RefundDenial::FullyRefunded,
+        };
+
+        // 退款已生效但订单状态尚未更新
+        assert_eq!(order.refundability(Money::from_cents(1000)), fully_refunded);
+
+        order.mark_as_refunded().unwrap();
+        assert_eq!(order.refundability(Money::from_cents(1000)), fully_refunded);
+    }
+
+    #[test]
+    fn test_refundability_denied_after_refund_window() {
+        let order = succeeded_order();
+        let paid_at = order.paid_at.unwrap();
+
+        let last_day = paid_at + chrono::Duration::days(limits::REFUND_WINDOW_DAYS);
+        assert!(matches!(
+            order.refundability_at(Money::from_cents(0), last_day),
+            Refundability::Allowed { .. }
+        ));
+
+        let expired = last_day + chrono::Duration::seconds(1);
+        assert_eq!(
+            order.refundability_at(Money::from_cents(0), expired),
+            Refundability::Denied {
+                reason: RefundDenial::TooOld
+            }
+        );
+    }
+
+    #[test]
+    fn test_goods_tag_validation() {
+        let order = order_with("ORDER123", "测试商品", None)
+            .unwrap()
+            .with_goods_tag(Some("WXG_2023-spring".to_string()))
+            .unwrap();
+        assert_eq!(order.goods_tag.as_deref(), Some("WXG_2023-spring"));
+
+        let order = order_with("ORDER123", "测试商品", None).unwrap();
+        assert!(order.clone().with_goods_tag(Some("a".repeat(32))).is_ok());
+        for invalid in ["", "优惠券", "WXG 2023", "a".repeat(33).as_str()] {
+            let result = order.clone().with_goods_tag(Some(invalid.to_string()));
+            assert!(
+                matches!(result, Err(DomainError::ValidationError(_))),
+                "{:?}",
+                invalid
+            );
+        }
+    }
+
     #[test]
     fn test_invalid_amount() {
         let result = PaymentOrder::new(
+            "1900000109".to_string(),
             "ORDER123".to_string(),
             Money::from_cents(0),
             PaymentMethod::MiniProgram,
@@ -239,4 +775,118 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    fn order_with(
+        out_order_no: &str,
+        description: &str,
+        attach: Option<&str>,
+    ) -> DomainResult<PaymentOrder> {
+        PaymentOrder::new(
+            "1900000109".to_string(),
+            out_order_no.to_string(),
+            Money::from_yuan(10),
+            PaymentMethod::MiniProgram,
+            description.to_string(),
+            "127.0.0.1".to_string(),
+            Some("openid123".to_string()),
+            attach.map(String::from),
+        )
+    }
+
+    #[test]
+    fn test_out_order_no_length_boundary() {
+        let max = "A".repeat(limits::MAX_OUT_ORDER_NO_BYTES);
+        assert!(order_with(&max, "测试商品", None).is_ok());
+
+        let too_long = "A".repeat(limits::MAX_OUT_ORDER_NO_BYTES + 1);
+        assert!(matches!(
+            order_with(&too_long, "测试商品", None),
+            Err(DomainError::ValidationError(_))
+        ));
+        assert!(order_with("", "测试商品", None).is_err());
+    }
+
+    #[test]
+    fn test_whitespace_only_fields_rejected() {
+        assert!(matches!(
+            order_with("   ", "测试商品", None),
+            Err(DomainError::ValidationError(_))
+        ));
+        assert!(matches!(
+            order_with("ORDER123", " \t\n", None),
+            Err(DomainError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_padded_fields_are_trimmed() {
+        let order = order_with("  ORDER123\t", "  测试商品 ", None).unwrap();
+        assert_eq!(order.out_order_no, "ORDER123");
+        assert_eq!(order.description, "测试商品");
+
+        // 长度按去除空白后计算
+        let padded = format!(" {} ", "A".repeat(limits::MAX_OUT_ORDER_NO_BYTES));
+        assert!(order_with(&padded, "测试商品", None).is_ok());
+    }
+
+    #[test]
+    fn test_description_length_is_counted_in_bytes() {
+        // 42个汉字占126字节，再加1个ASCII字符恰好达到127字节上限
+        let max = format!("{}a", "测".repeat(42));
+        assert_eq!(max.len(), limits::MAX_DESCRIPTION_BYTES);
+        assert!(order_with("ORDER123", &max, None).is_ok());
+
+        // 43个汉字只有43个字符，但占129字节
+        let too_long = "测".repeat(43);
+        assert!(too_long.chars().count() < limits::MAX_DESCRIPTION_BYTES);
+        assert!(order_with("ORDER123", &too_long, None).is_err());
+    }
+
+    #[test]
+    fn test_attach_length_boundary() {
+        let max = "a".repeat(limits::MAX_ATTACH_BYTES);
+        assert!(order_with("ORDER123", "测试商品", Some(&max)).is_ok());
+
+        let too_long = "a".repeat(limits::MAX_ATTACH_BYTES + 1);
+        assert!(order_with("ORDER123", "测试商品", Some(&too_long)).is_err());
+    }
+
+    #[test]
+    fn test_client_ip_length_boundary() {
+        let order = |client_ip: String| {
+            PaymentOrder::new(
+                "1900000109".to_string(),
+                "ORDER123".to_string(),
+                Money::from_yuan(10),
+                PaymentMethod::MiniProgram,
+                "测试商品".to_string(),
+                client_ip,
+                Some("openid123".to_string()),
+                None,
+            )
+        };
+
+        assert!(order("a".repeat(limits::MAX_CLIENT_IP_BYTES)).is_ok());
+        assert!(matches!(
+            order("a".repeat(limits::MAX_CLIENT_IP_BYTES + 1)),
+            Err(DomainError::ValidationError(_))
+        ));
+        assert!(order(String::new()).is_err());
+    }
+
+    #[test]
+    fn test_refund_reason_length_boundary() {
+        let order = order_with("ORDER123", "测试商品", None).unwrap();
+        let refund = |reason: String| {
+            RefundRecord::new(
+                &order,
+                "RF123".to_string(),
+                Money::from_cents(100),
+                Some(reason),
+            )
+        };
+
+        assert!(refund("a".repeat(limits::MAX_REFUND_REASON_BYTES)).is_ok());
+        assert!(refund("a".repeat(limits::MAX_REFUND_REASON_BYTES + 1)).is_err());
+    }
 }
@@ -23,10 +23,26 @@ pub enum DomainError {
     #[error("Signature verification failed")]
     SignatureVerificationFailed,
 
+    /// 尚未加载任何平台证书（如冷启动期间），无法验签
+    #[error("Platform certificate unavailable for serial: {0}")]
+    CertificateUnavailable(String),
+
     /// 微信支付API错误
     #[error("WeChat Pay API error: {0}")]
     WeChatPayError(String),
 
+    /// 微信支付接口限流
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
+    /// 微信支付服务暂时不可用（系统错误或5xx）
+    #[error("Service unavailable: {0}")]
+    ServiceUnavailable(String),
+
+    /// 商户号与订单不匹配（订单不属于当前配置的商户）
+    #[error("Merchant mismatch: {0}")]
+    MerchantMismatch(String),
+
     /// 数据库错误
     #[error("Database error: {0}")]
     DatabaseError(#[from] sqlx::Error),
@@ -52,5 +68,73 @@ pub enum DomainError {
     InternalError(String),
 }
 
+impl DomainError {
+    /// 以相同参数重试是否可能成功（超时、限流、连接池耗尽等临时性错误）
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DomainError::RateLimited(_)
+            | DomainError::ServiceUnavailable(_)
+            | DomainError::CertificateUnavailable(_) => true,
+            DomainError::DatabaseError(e) => matches!(
+                e,
+                sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_)
+            ),
+            DomainError::HttpError(e) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// 建议的重试等待时间（毫秒）
+    pub fn retry_after_ms(&self) -> Option<u64> {
+        match self {
+            DomainError::RateLimited(_) => Some(1000),
+            DomainError::DatabaseError(sqlx::Error::PoolTimedOut) => Some(500),
+            _ => None,
+        }
+    }
+}
+
 /// 领域结果类型
 pub type DomainResult<T> = Result<T, DomainError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transient_errors_are_retryable() {
+        let error = DomainError::RateLimited("FREQUENCY_LIMITED".to_string());
+        assert!(error.is_retryable());
+        assert_eq!(error.retry_after_ms(), Some(1000));
+
+        let error = DomainError::DatabaseError(sqlx::Error::PoolTimedOut);
+        assert!(error.is_retryable());
+        assert_eq!(error.retry_after_ms(), Some(500));
+
+        let error = DomainError::ServiceUnavailable("SYSTEM_ERROR".to_string());
+        assert!(error.is_retryable());
+        assert_eq!(error.retry_after_ms(), None);
+
+        let error = DomainError::CertificateUnavailable("PUB_KEY_ID_01".to_string());
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_client_errors_are_not_retryable() {
+        let errors = [
+            DomainError::ValidationError("Description must be 1-127 bytes, got 0".to_string()),
+            DomainError::InvalidAmount("Amount must be positive".to_string()),
+            DomainError::InvalidState {
+                expected: "pending".to_string(),
+                actual: "succeeded".to_string(),
+            },
+            DomainError::OrderNotFound("ORDER123".to_string()),
+            DomainError::DatabaseError(sqlx::Error::RowNotFound),
+        ];
+
+        for error in errors {
+            assert!(!error.is_retryable(), "{} should not be retryable", error);
+            assert_eq!(error.retry_after_ms(), None);
+        }
+    }
+}
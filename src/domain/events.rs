@@ -1,4 +1,4 @@
-use crate::domain::entities::PaymentOrder;
+use crate::domain::entities::{PaymentOrder, RefundRecord};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -15,6 +15,7 @@ pub struct PaymentOrderCreated {
     pub event_id: Uuid,
     pub occurred_at: DateTime<Utc>,
     pub order_id: Uuid,
+    pub merchant_id: String,
     pub out_order_no: String,
     pub amount: i64,
 }
@@ -35,6 +36,7 @@ impl PaymentOrderCreated {
             event_id: Uuid::new_v4(),
             occurred_at: Utc::now(),
             order_id: order.id,
+            merchant_id: order.merchant_id.clone(),
             out_order_no: order.out_order_no.clone(),
             amount: order.amount.to_cents(),
         }
@@ -47,6 +49,7 @@ pub struct PaymentSucceeded {
     pub event_id: Uuid,
     pub occurred_at: DateTime<Utc>,
     pub order_id: Uuid,
+    pub merchant_id: String,
     pub out_order_no: String,
     pub transaction_id: String,
     pub amount: i64,
@@ -68,6 +71,7 @@ impl PaymentSucceeded {
             event_id: Uuid::new_v4(),
             occurred_at: Utc::now(),
             order_id: order.id,
+            merchant_id: order.merchant_id.clone(),
             out_order_no: order.out_order_no.clone(),
             transaction_id: order
                 .transaction_id
@@ -84,6 +88,7 @@ pub struct PaymentFailed {
     pub event_id: Uuid,
     pub occurred_at: DateTime<Utc>,
     pub order_id: Uuid,
+    pub merchant_id: String,
     pub out_order_no: String,
     pub reason: String,
 }
@@ -104,8 +109,83 @@ impl PaymentFailed {
             event_id: Uuid::new_v4(),
             occurred_at: Utc::now(),
             order_id: order.id,
+            merchant_id: order.merchant_id.clone(),
             out_order_no: order.out_order_no.clone(),
             reason,
         }
     }
 }
+
+/// 退款成功事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentRefunded {
+    pub event_id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub order_id: Uuid,
+    pub merchant_id: String,
+    pub out_order_no: String,
+    pub out_refund_no: String,
+    pub refund_amount: i64,
+}
+
+impl DomainEvent for PaymentRefunded {
+    fn event_type(&self) -> &'static str {
+        "PaymentRefunded"
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+}
+
+impl PaymentRefunded {
+    pub fn from_refund(order: &PaymentOrder, refund: &RefundRecord) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            occurred_at: Utc::now(),
+            order_id: order.id,
+            merchant_id: order.merchant_id.clone(),
+            out_order_no: order.out_order_no.clone(),
+            out_refund_no: refund.out_refund_no.clone(),
+            refund_amount: refund.amount.to_cents(),
+        }
+    }
+}
+
+/// 退款失败事件（退款关闭或异常）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefundFailed {
+    pub event_id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub order_id: Uuid,
+    pub merchant_id: String,
+    pub out_order_no: String,
+    pub out_refund_no: String,
+    pub refund_amount: i64,
+    pub reason: String,
+}
+
+impl DomainEvent for RefundFailed {
+    fn event_type(&self) -> &'static str {
+        "RefundFailed"
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+}
+
+impl RefundFailed {
+    pub fn new(order: &PaymentOrder, refund: &RefundRecord, reason: String) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            occurred_at: Utc::now(),
+            order_id: order.id,
+            merchant_id: order.merchant_id.clone(),
+            out_order_no: order.out_order_no.clone(),
+            out_refund_no: refund.out_refund_no.clone(),
+            refund_amount: refund.amount.to_cents(),
+            reason,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::domain::errors::{DomainError, DomainResult};
+
+/// 商户号最大长度（字节）
+pub const MAX_MERCHANT_ID_BYTES: usize = 32;
+
+/// 商户订单号最大长度（字节，微信支付限制）
+pub const MAX_OUT_ORDER_NO_BYTES: usize = 32;
+
+/// 商品描述最大长度（字节，微信支付限制）
+pub const MAX_DESCRIPTION_BYTES: usize = 127;
+
+/// 附加数据最大长度（字节，微信支付限制）
+pub const MAX_ATTACH_BYTES: usize = 128;
+
+/// 订单优惠标记最大长度（字节，微信支付限制）
+pub const MAX_GOODS_TAG_BYTES: usize = 32;
+
+/// 用户OpenID最大长度（字节）
+pub const MAX_OPENID_BYTES: usize = 128;
+
+/// 客户端IP最大长度（字节，IPv6 文本形式最长 45 字节，与数据库列宽一致）
+pub const MAX_CLIENT_IP_BYTES: usize = 45;
+
+/// 商户退款单号最大长度（字节，微信支付限制）
+pub const MAX_OUT_REFUND_NO_BYTES: usize = 64;
+
+/// 退款原因最大长度（字节，微信支付限制）
+pub const MAX_REFUND_REASON_BYTES: usize = 80;
+
+/// 微信支付 APIv3 密钥长度（字节，AES-256）
+pub const API_V3_KEY_BYTES: usize = 32;
+
+/// 回调报文 AES-GCM 随机串长度（字节）
+pub const AES_GCM_NONCE_BYTES: usize = 12;
+
+/// 退款时限（天，微信支付限制：交易完成超过一年不能退款）
+pub const REFUND_WINDOW_DAYS: i64 = 365;
+
+/// 校验必填字段长度为 1..=max 字节
+pub fn check_required(field: &str, value: &str, max: usize) -> DomainResult<()> {
+    if value.is_empty() || value.len() > max {
+        return Err(DomainError::ValidationError(format!(
+            "{} must be 1-{} bytes, got {}",
+            field,
+            max,
+            value.len()
+        )));
+    }
+    Ok(())
+}
+
+/// 校验可选字段长度不超过 max 字节
+pub fn check_optional(field: &str, value: Option<&str>, max: usize) -> DomainResult<()> {
+    match value {
+        Some(value) if value.len() > max => Err(DomainError::ValidationError(format!(
+            "{} must be at most {} bytes, got {}",
+            field,
+            max,
+            value.len()
+        ))),
+        _ => Ok(()),
+    }
+}
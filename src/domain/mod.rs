@@ -1,9 +1,15 @@
 pub mod entities;
 pub mod errors;
 pub mod events;
+pub mod limits;
 pub mod value_objects;
 
-pub use entities::PaymentOrder;
+pub use entities::{
+    DeadLetterNotification, OutboxEvent, PaymentOrder, RefundRecord, StateTransition, WebhookEvent,
+};
 pub use errors::{DomainError, DomainResult};
 pub use events::*;
-pub use value_objects::{Money, PaymentMethod, PaymentState};
+pub use value_objects::{
+    Money, OutboxState, PaymentMethod, PaymentState, RefundDenial, RefundState, Refundability,
+    RoundingMode,
+};
@@ -1,3 +1,4 @@
+use crate::domain::errors::{DomainError, DomainResult};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -32,6 +33,53 @@ impl fmt::Display for PaymentState {
     }
 }
 
+/// 退款状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefundState {
+    /// 退款处理中
+    Processing,
+    /// 退款成功
+    Succeeded,
+    /// 退款关闭
+    Closed,
+    /// 退款异常
+    Abnormal,
+}
+
+impl fmt::Display for RefundState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RefundState::Processing => write!(f, "processing"),
+            RefundState::Succeeded => write!(f, "succeeded"),
+            RefundState::Closed => write!(f, "closed"),
+            RefundState::Abnormal => write!(f, "abnormal"),
+        }
+    }
+}
+
+/// 发件箱事件状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxState {
+    /// 待投递
+    Pending,
+    /// 已投递
+    Published,
+    /// 超过最大重试次数，停止投递
+    Poison,
+}
+
+impl fmt::Display for OutboxState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutboxState::Pending => write!(f, "pending"),
+            OutboxState::Published => write!(f, "published"),
+            OutboxState::Poison => write!(f, "poison"),
+        }
+    }
+}
+
 /// 支付方式
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -57,6 +105,74 @@ impl fmt::Display for PaymentMethod {
     }
 }
 
+/// 金额展示的舍入方式（仅用于最终格式化，汇总过程始终使用整数分）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// 四舍五入
+    #[default]
+    HalfUp,
+    /// 四舍六入五成双（银行家舍入）
+    HalfEven,
+    /// 直接截断
+    Down,
+}
+
+impl RoundingMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "half_up" => Some(RoundingMode::HalfUp),
+            "half_even" => Some(RoundingMode::HalfEven),
+            "down" => Some(RoundingMode::Down),
+            _ => None,
+        }
+    }
+
+    /// 将 `value / divisor` 按舍入方式取整
+    fn divide(self, value: u64, divisor: u64) -> u64 {
+        let (quotient, remainder) = (value / divisor, value % divisor);
+        let round_up = match self {
+            RoundingMode::HalfUp => remainder * 2 >= divisor,
+            RoundingMode::HalfEven => {
+                remainder * 2 > divisor || (remainder * 2 == divisor && quotient % 2 == 1)
+            }
+            RoundingMode::Down => false,
+        };
+        if round_up { quotient + 1 } else { quotient }
+    }
+}
+
+/// 订单不可退款的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefundDenial {
+    /// 订单未支付成功
+    NotSucceeded,
+    /// 订单已全额退款
+    FullyRefunded,
+    /// 交易完成已超过微信支付的退款时限
+    TooOld,
+}
+
+impl fmt::Display for RefundDenial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RefundDenial::NotSucceeded => write!(f, "not_succeeded"),
+            RefundDenial::FullyRefunded => write!(f, "fully_refunded"),
+            RefundDenial::TooOld => write!(f, "too_old"),
+        }
+    }
+}
+
+/// 订单可退款情况
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Refundability {
+    /// 可以退款，`max` 为剩余可退金额
+    Allowed { max: Money },
+    /// 不可退款
+    Denied { reason: RefundDenial },
+}
+
 /// 货币金额（分为单位，避免浮点数精度问题）
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Money {
@@ -72,11 +188,34 @@ impl Money {
         }
     }
 
-    /// 创建新的金额对象（单位：分）
+    /// 单笔金额上限（分），即1亿元
+    pub const MAX_CENTS: i64 = 10_000_000_000;
+
+    /// 创建新的金额对象（单位：分），仅用于内部可信数据
     pub fn from_cents(cents: i64) -> Self {
         Self { amount_cents: cents }
     }
 
+    /// 从外部输入创建金额对象（单位：分），拒绝负数和超出上限的金额
+    pub fn try_from_cents(cents: i64) -> DomainResult<Self> {
+        if cents < 0 {
+            return Err(DomainError::InvalidAmount(format!(
+                "Amount must not be negative: {}",
+                cents
+            )));
+        }
+        if cents > Self::MAX_CENTS {
+            return Err(DomainError::InvalidAmount(format!(
+                "Amount exceeds maximum of {} cents: {}",
+                Self::MAX_CENTS,
+                cents
+            )));
+        }
+        Ok(Self {
+            amount_cents: cents,
+        })
+    }
+
     /// 转换为元
     pub fn to_yuan(&self) -> f64 {
         self.amount_cents as f64 / 100.0
@@ -86,11 +225,39 @@ impl Money {
     pub fn to_cents(&self) -> i64 {
         self.amount_cents
     }
+
+    /// 金额相加（用于汇总，不受单笔上限限制），溢出时返回错误
+    pub fn checked_add(self, other: Money) -> DomainResult<Money> {
+        self.amount_cents
+            .checked_add(other.amount_cents)
+            .map(Money::from_cents)
+            .ok_or_else(|| DomainError::InvalidAmount("Amount sum overflow".to_string()))
+    }
+
+    /// 按 `scale` 位小数（0-2）格式化为元，全程使用整数运算
+    pub fn format_yuan(&self, scale: u32, mode: RoundingMode) -> String {
+        let scale = scale.min(2);
+        let unit = 10_u64.pow(scale);
+        let sign = if self.amount_cents < 0 { "-" } else { "" };
+        let scaled = mode.divide(self.amount_cents.unsigned_abs(), 10_u64.pow(2 - scale));
+
+        if scale == 0 {
+            format!("{}{}", sign, scaled)
+        } else {
+            format!(
+                "{}{}.{:0width$}",
+                sign,
+                scaled / unit,
+                scaled % unit,
+                width = scale as usize
+            )
+        }
+    }
 }
 
 impl fmt::Display for Money {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "¥{:.2}", self.to_yuan())
+        write!(f, "¥{}", self.format_yuan(2, RoundingMode::Down))
     }
 }
 
@@ -109,5 +276,61 @@ mod tests {
     fn test_money_display() {
         let money = Money::from_yuan(10);
         assert_eq!(format!("{}", money), "¥10.00");
+        assert_eq!(format!("{}", Money::from_cents(-5)), "¥-0.05");
+    }
+
+    #[test]
+    fn test_money_format_yuan_rounding_modes() {
+        let cases = [
+            (1250, RoundingMode::HalfUp, "13"),
+            (1250, RoundingMode::HalfEven, "12"),
+            (1350, RoundingMode::HalfEven, "14"),
+            (1299, RoundingMode::Down, "12"),
+            (-1250, RoundingMode::HalfUp, "-13"),
+        ];
+        for (cents, mode, expected) in cases {
+            assert_eq!(Money::from_cents(cents).format_yuan(0, mode), expected);
+        }
+
+        assert_eq!(
+            Money::from_cents(1235).format_yuan(1, RoundingMode::HalfUp),
+            "12.4"
+        );
+        assert_eq!(
+            Money::from_cents(1225).format_yuan(1, RoundingMode::HalfEven),
+            "12.2"
+        );
+        assert_eq!(
+            Money::from_cents(7).format_yuan(2, RoundingMode::Down),
+            "0.07"
+        );
+    }
+
+    #[test]
+    fn test_money_try_from_cents() {
+        assert_eq!(Money::try_from_cents(0).unwrap().to_cents(), 0);
+        assert_eq!(Money::try_from_cents(1000).unwrap().to_cents(), 1000);
+        assert_eq!(
+            Money::try_from_cents(Money::MAX_CENTS).unwrap().to_cents(),
+            Money::MAX_CENTS
+        );
+    }
+
+    #[test]
+    fn test_money_try_from_cents_rejects_negative() {
+        assert!(matches!(
+            Money::try_from_cents(-1),
+            Err(DomainError::InvalidAmount(_))
+        ));
+        assert!(Money::try_from_cents(i64::MIN).is_err());
+    }
+
+    #[test]
+    fn test_money_try_from_cents_rejects_overflow() {
+        assert!(matches!(
+            Money::try_from_cents(Money::MAX_CENTS + 1),
+            Err(DomainError::InvalidAmount(_))
+        ));
+        assert!(Money::try_from_cents(i64::MAX).is_err());
     }
 }
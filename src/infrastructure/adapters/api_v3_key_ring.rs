@@ -0,0 +1,94 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::limits;
+use std::sync::{Arc, RwLock};
+
+/// 最多保留的 APIv3 密钥数量（当前密钥 + 过渡期内的旧密钥）
+const MAX_KEYS: usize = 3;
+
+/// APIv3 密钥环：回调解密时按顺序尝试，轮换时新密钥置于最前，旧密钥保留用于过渡期
+///
+/// 克隆后共享同一组密钥，运行时轮换对所有持有者立即生效。
+#[derive(Clone, Default)]
+pub struct ApiV3KeyRing {
+    keys: Arc<RwLock<Vec<String>>>,
+}
+
+impl ApiV3KeyRing {
+    /// 以当前密钥与旧密钥（按从新到旧）创建
+    pub fn new(current: &str, previous: &[String]) -> Self {
+        let mut keys = vec![current.to_string()];
+        for key in previous {
+            if !keys.contains(key) && keys.len() < MAX_KEYS {
+                keys.push(key.clone());
+            }
+        }
+
+        Self {
+            keys: Arc::new(RwLock::new(keys)),
+        }
+    }
+
+    /// 轮换密钥：新密钥置于最前，超出数量上限的最旧密钥被移除
+    pub fn rotate(&self, key: &str) -> DomainResult<()> {
+        check_key(key)?;
+
+        let mut keys = self.keys.write().expect("key ring lock poisoned");
+        keys.retain(|existing| existing != key);
+        keys.insert(0, key.to_string());
+        keys.truncate(MAX_KEYS);
+        Ok(())
+    }
+
+    /// 按从新到旧的顺序返回全部密钥
+    pub fn keys(&self) -> Vec<String> {
+        self.keys.read().expect("key ring lock poisoned").clone()
+    }
+}
+
+/// APIv3 密钥必须恰好为32字节（AES-256）
+fn check_key(key: &str) -> DomainResult<()> {
+    if key.len() != limits::API_V3_KEY_BYTES {
+        return Err(DomainError::ValidationError(format!(
+            "api_v3_key must be {} bytes, got {}",
+            limits::API_V3_KEY_BYTES,
+            key.len()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_A: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const KEY_B: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+    const KEY_C: &str = "cccccccccccccccccccccccccccccccc";
+    const KEY_D: &str = "dddddddddddddddddddddddddddddddd";
+
+    #[test]
+    fn test_rotate_puts_new_key_first_and_caps_history() {
+        let ring = ApiV3KeyRing::new(KEY_A, &[]);
+
+        ring.rotate(KEY_B).unwrap();
+        ring.rotate(KEY_C).unwrap();
+        assert_eq!(ring.keys(), vec![KEY_C, KEY_B, KEY_A]);
+
+        ring.rotate(KEY_D).unwrap();
+        assert_eq!(ring.keys(), vec![KEY_D, KEY_C, KEY_B]);
+
+        // 重新启用旧密钥不会产生重复
+        ring.rotate(KEY_B).unwrap();
+        assert_eq!(ring.keys(), vec![KEY_B, KEY_D, KEY_C]);
+    }
+
+    #[test]
+    fn test_rotate_rejects_invalid_length() {
+        let ring = ApiV3KeyRing::new(KEY_A, &[]);
+        assert!(matches!(
+            ring.rotate("too-short"),
+            Err(DomainError::ValidationError(_))
+        ));
+        assert_eq!(ring.keys(), vec![KEY_A]);
+    }
+}
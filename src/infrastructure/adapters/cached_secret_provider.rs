@@ -0,0 +1,86 @@
+use crate::domain::errors::DomainResult;
+use crate::infrastructure::adapters::clock::SystemClock;
+use crate::ports::{Clock, SecretProvider};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// 带过期时间的密钥缓存，避免每次读取都访问远程密钥服务
+pub struct CachedSecretProvider {
+    inner: Arc<dyn SecretProvider>,
+    ttl: chrono::Duration,
+    clock: Arc<dyn Clock>,
+    cache: RwLock<HashMap<String, (String, DateTime<Utc>)>>,
+}
+
+impl CachedSecretProvider {
+    pub fn new(inner: Arc<dyn SecretProvider>, ttl: chrono::Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            clock: Arc::new(SystemClock),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 替换时钟（测试中注入固定时钟）
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+#[async_trait]
+impl SecretProvider for CachedSecretProvider {
+    async fn get(&self, key: &str) -> DomainResult<String> {
+        let now = self.clock.now();
+        if let Some((value, expires_at)) =
+            self.cache.read().expect("secret cache poisoned").get(key)
+            && *expires_at > now
+        {
+            return Ok(value.clone());
+        }
+
+        let value = self.inner.get(key).await?;
+        self.cache
+            .write()
+            .expect("secret cache poisoned")
+            .insert(key.to_string(), (value.clone(), now + self.ttl));
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::adapters::{FixedClock, MockSecretProvider};
+
+    fn cached(inner: &MockSecretProvider, ttl_secs: i64) -> CachedSecretProvider {
+        CachedSecretProvider::new(Arc::new(inner.clone()), chrono::Duration::seconds(ttl_secs))
+            .with_clock(Arc::new(FixedClock::at_timestamp(1_703_642_400)))
+    }
+
+    #[tokio::test]
+    async fn test_secret_cached_within_ttl() {
+        let inner = MockSecretProvider::new().with_secret("WECHAT_API_V3_KEY", "key");
+        let provider = cached(&inner, 300);
+
+        assert_eq!(provider.get("WECHAT_API_V3_KEY").await.unwrap(), "key");
+        assert_eq!(provider.get("WECHAT_API_V3_KEY").await.unwrap(), "key");
+        assert_eq!(inner.fetches(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_secret_refetched() {
+        let inner = MockSecretProvider::new().with_secret("WECHAT_API_V3_KEY", "key");
+        let provider = cached(&inner, 0);
+
+        provider.get("WECHAT_API_V3_KEY").await.unwrap();
+        provider.get("WECHAT_API_V3_KEY").await.unwrap();
+        assert_eq!(inner.fetches(), 2);
+
+        // 读取失败不会写入缓存
+        assert!(provider.get("MISSING").await.is_err());
+    }
+}
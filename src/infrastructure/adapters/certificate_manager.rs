@@ -0,0 +1,157 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use base64::Engine;
+use rsa::RsaPublicKey;
+use rsa::pkcs1v15::{Signature, VerifyingKey};
+use rsa::pkcs8::DecodePublicKey;
+use rsa::sha2::Sha256;
+use rsa::signature::Verifier;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use tracing::warn;
+
+/// 微信支付平台证书管理（按序列号保存平台公钥，用于回调验签）
+#[derive(Clone, Default)]
+pub struct CertificateManager {
+    keys: Arc<RwLock<HashMap<String, RsaPublicKey>>>,
+    /// 允许的序列号，None 表示接受所有已加载的证书
+    allowed_serials: Option<HashSet<String>>,
+}
+
+impl CertificateManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 限制可接受的序列号（为空时不做限制）
+    pub fn with_allowed_serials<I>(mut self, serials: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let serials: HashSet<String> = serials.into_iter().collect();
+        self.allowed_serials = (!serials.is_empty()).then_some(serials);
+        self
+    }
+
+    /// 加载平台公钥（PEM格式）
+    pub fn add_public_key_pem(&self, serial: &str, pem: &str) -> DomainResult<()> {
+        let key = RsaPublicKey::from_public_key_pem(pem).map_err(|e| {
+            DomainError::CryptoError(format!("Failed to load platform public key: {}", e))
+        })?;
+        self.keys
+            .write()
+            .expect("certificate lock poisoned")
+            .insert(serial.to_string(), key);
+        Ok(())
+    }
+
+    /// 序列号是否在允许列表中
+    pub fn is_allowed(&self, serial: &str) -> bool {
+        self.allowed_serials
+            .as_ref()
+            .is_none_or(|allowed| allowed.contains(serial))
+    }
+
+    /// 使用指定序列号的平台公钥验证签名（SHA256withRSA）；尚未加载任何平台证书时
+    /// 返回 `CertificateUnavailable`，由调用方让微信稍后重试
+    pub fn verify(&self, serial: &str, message: &str, signature: &str) -> DomainResult<bool> {
+        if !self.is_allowed(serial) {
+            warn!(
+                "Rejected signature with serial not in allowlist: {}",
+                serial
+            );
+            return Ok(false);
+        }
+
+        let keys = self.keys.read().expect("certificate lock poisoned");
+        if keys.is_empty() {
+            return Err(DomainError::CertificateUnavailable(serial.to_string()));
+        }
+        let Some(key) = keys.get(serial) else {
+            warn!("No platform certificate loaded for serial: {}", serial);
+            return Ok(false);
+        };
+
+        let Ok(signature_bytes) = base64::engine::general_purpose::STANDARD.decode(signature)
+        else {
+            return Ok(false);
+        };
+        let Ok(signature) = Signature::try_from(signature_bytes.as_slice()) else {
+            return Ok(false);
+        };
+
+        let verifying_key = VerifyingKey::<Sha256>::new(key.clone());
+        Ok(verifying_key.verify(message.as_bytes(), &signature).is_ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+    use rsa::RsaPrivateKey;
+    use rsa::pkcs1v15::SigningKey;
+    use rsa::pkcs8::{EncodePublicKey, LineEnding};
+    use rsa::signature::{RandomizedSigner, SignatureEncoding};
+
+    const MESSAGE: &str = "1703642400\nfdasflkja484\n{}\n";
+
+    fn keypair() -> (RsaPrivateKey, String) {
+        let private_key = RsaPrivateKey::new(&mut OsRng, 1024).unwrap();
+        let pem = private_key
+            .to_public_key()
+            .to_public_key_pem(LineEnding::LF)
+            .unwrap();
+        (private_key, pem)
+    }
+
+    fn sign(private_key: &RsaPrivateKey, message: &str) -> String {
+        let signing_key = SigningKey::<Sha256>::new(private_key.clone());
+        let signature = signing_key.sign_with_rng(&mut OsRng, message.as_bytes());
+        base64::engine::general_purpose::STANDARD.encode(signature.to_bytes())
+    }
+
+    #[test]
+    fn test_verify_accepts_all_loaded_serials_by_default() {
+        let (private_key, pem) = keypair();
+        let manager = CertificateManager::new();
+        manager.add_public_key_pem("SERIAL_A", &pem).unwrap();
+
+        let signature = sign(&private_key, MESSAGE);
+        assert!(manager.verify("SERIAL_A", MESSAGE, &signature).unwrap());
+        assert!(!manager.verify("SERIAL_A", "tampered", &signature).unwrap());
+        assert!(!manager.verify("SERIAL_B", MESSAGE, &signature).unwrap());
+    }
+
+    #[test]
+    fn test_verify_without_certificates_is_unavailable() {
+        let (private_key, _) = keypair();
+        let manager = CertificateManager::new();
+
+        let result = manager.verify("SERIAL_A", MESSAGE, &sign(&private_key, MESSAGE));
+        assert!(matches!(
+            result,
+            Err(DomainError::CertificateUnavailable(serial)) if serial == "SERIAL_A"
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_serial_not_in_allowlist() {
+        let (key_a, pem_a) = keypair();
+        let (key_b, pem_b) = keypair();
+        let manager = CertificateManager::new().with_allowed_serials(vec!["SERIAL_A".to_string()]);
+        manager.add_public_key_pem("SERIAL_A", &pem_a).unwrap();
+        manager.add_public_key_pem("SERIAL_B", &pem_b).unwrap();
+
+        assert!(
+            manager
+                .verify("SERIAL_A", MESSAGE, &sign(&key_a, MESSAGE))
+                .unwrap()
+        );
+        // 证书存在且签名正确，但序列号不在允许列表中
+        assert!(
+            !manager
+                .verify("SERIAL_B", MESSAGE, &sign(&key_b, MESSAGE))
+                .unwrap()
+        );
+    }
+}
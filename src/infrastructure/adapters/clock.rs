@@ -0,0 +1,33 @@
+use crate::ports::Clock;
+use chrono::{DateTime, Utc};
+
+/// 系统时钟
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// 固定时钟（用于测试，使签名时间戳与新鲜度校验可复现）
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(DateTime<Utc>);
+
+impl FixedClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self(now)
+    }
+
+    /// 以秒级Unix时间戳创建
+    pub fn at_timestamp(secs: i64) -> Self {
+        Self(DateTime::from_timestamp(secs, 0).expect("timestamp out of range"))
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
@@ -0,0 +1,21 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::ports::SecretProvider;
+use async_trait::async_trait;
+
+/// 环境变量密钥提供方（默认），密钥名即环境变量名
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvSecretProvider;
+
+impl EnvSecretProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SecretProvider for EnvSecretProvider {
+    async fn get(&self, key: &str) -> DomainResult<String> {
+        std::env::var(key)
+            .map_err(|_| DomainError::ConfigurationError(format!("{} must be set", key)))
+    }
+}
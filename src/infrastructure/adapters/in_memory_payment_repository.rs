@@ -0,0 +1,605 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{
+    DeadLetterNotification, OutboxEvent, OutboxState, PaymentOrder, PaymentState, RefundRecord,
+    StateTransition, WebhookEvent,
+};
+use crate::ports::payment_repository_port::{
+    OrderExportFilter, OrderListQuery, OrderPage, OrderSortField, OrderStream,
+    PaymentRepositoryPort, PendingCursor, SortDirection, UnitOfWork,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tracing::debug;
+
+/// 内存支付订单仓储实现（用于本地开发和测试）
+#[derive(Clone, Default)]
+pub struct InMemoryPaymentRepository {
+    orders: Arc<RwLock<HashMap<uuid::Uuid, PaymentOrder>>>,
+    refunds: Arc<RwLock<Vec<RefundRecord>>>,
+    webhook_events: Arc<RwLock<Vec<WebhookEvent>>>,
+    dead_letters: Arc<RwLock<Vec<DeadLetterNotification>>>,
+    outbox: Arc<RwLock<Vec<OutboxEvent>>>,
+    transitions: Arc<RwLock<Vec<StateTransition>>>,
+}
+
+impl InMemoryPaymentRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 已保存的回调通知审计记录
+    pub fn webhook_events(&self) -> Vec<WebhookEvent> {
+        self.webhook_events
+            .read()
+            .expect("repository lock poisoned")
+            .clone()
+    }
+
+    /// 发件箱中的全部事件
+    pub fn outbox_events(&self) -> Vec<OutboxEvent> {
+        self.outbox
+            .read()
+            .expect("repository lock poisoned")
+            .clone()
+    }
+
+    /// 对已存在的订单执行修改
+    fn modify<F>(&self, id: uuid::Uuid, f: F) -> DomainResult<()>
+    where
+        F: FnOnce(&mut PaymentOrder),
+    {
+        let mut orders = self.orders.write().expect("repository lock poisoned");
+        modify_order(&mut orders, id, f)
+    }
+}
+
+type Orders = HashMap<uuid::Uuid, PaymentOrder>;
+
+/// 对已存在的订单执行修改
+fn modify_order<F>(orders: &mut Orders, id: uuid::Uuid, f: F) -> DomainResult<()>
+where
+    F: FnOnce(&mut PaymentOrder),
+{
+    let stored = orders
+        .get_mut(&id)
+        .ok_or_else(|| DomainError::OrderNotFound(id.to_string()))?;
+    f(stored);
+    Ok(())
+}
+
+fn apply_state(stored: &mut PaymentOrder, order: &PaymentOrder) {
+    stored.state = order.state;
+    stored.updated_at = order.updated_at;
+}
+
+fn apply_transaction(stored: &mut PaymentOrder, order: &PaymentOrder) {
+    stored.transaction_id = order.transaction_id.clone();
+    stored.paid_at = order.paid_at;
+    stored.state = order.state;
+    stored.updated_at = order.updated_at;
+}
+
+/// 保存退款记录（退款单号唯一）
+fn insert_refund(refunds: &mut Vec<RefundRecord>, refund: &RefundRecord) -> DomainResult<()> {
+    if refunds
+        .iter()
+        .any(|r| r.out_refund_no == refund.out_refund_no)
+    {
+        return Err(DomainError::ValidationError(format!(
+            "Duplicate out refund no: {}",
+            refund.out_refund_no
+        )));
+    }
+
+    refunds.push(refund.clone());
+    Ok(())
+}
+
+/// 更新退款结果
+fn modify_refund(refunds: &mut [RefundRecord], refund: &RefundRecord) -> DomainResult<()> {
+    let stored = refunds
+        .iter_mut()
+        .find(|r| r.id == refund.id)
+        .ok_or_else(|| DomainError::OrderNotFound(refund.out_refund_no.clone()))?;
+
+    stored.refund_id = refund.refund_id.clone();
+    stored.state = refund.state;
+    stored.updated_at = refund.updated_at;
+    Ok(())
+}
+
+/// 工作单元中暂存的写操作
+enum StagedWrite {
+    UpdateState(PaymentOrder),
+    SetTransaction(PaymentOrder),
+    SaveRefund(RefundRecord),
+    UpdateRefund(RefundRecord),
+    SaveOutboxEvent(OutboxEvent),
+    SaveTransition(StateTransition),
+}
+
+/// 内存工作单元（写操作暂存到提交时一次性应用，任一失败则全部不生效）
+pub struct InMemoryUnitOfWork {
+    repository: InMemoryPaymentRepository,
+    writes: Vec<StagedWrite>,
+}
+
+#[async_trait]
+impl UnitOfWork for InMemoryUnitOfWork {
+    async fn update_state(&mut self, order: &PaymentOrder) -> DomainResult<()> {
+        self.writes.push(StagedWrite::UpdateState(order.clone()));
+        Ok(())
+    }
+
+    async fn set_transaction(&mut self, order: &PaymentOrder) -> DomainResult<()> {
+        self.writes.push(StagedWrite::SetTransaction(order.clone()));
+        Ok(())
+    }
+
+    async fn save_refund(&mut self, refund: &RefundRecord) -> DomainResult<()> {
+        self.writes.push(StagedWrite::SaveRefund(refund.clone()));
+        Ok(())
+    }
+
+    async fn update_refund(&mut self, refund: &RefundRecord) -> DomainResult<()> {
+        self.writes.push(StagedWrite::UpdateRefund(refund.clone()));
+        Ok(())
+    }
+
+    async fn save_outbox_event(&mut self, event: &OutboxEvent) -> DomainResult<()> {
+        self.writes
+            .push(StagedWrite::SaveOutboxEvent(event.clone()));
+        Ok(())
+    }
+
+    async fn save_transition(&mut self, transition: &StateTransition) -> DomainResult<()> {
+        self.writes
+            .push(StagedWrite::SaveTransition(transition.clone()));
+        Ok(())
+    }
+
+    async fn commit(self) -> DomainResult<()> {
+        let mut orders = self
+            .repository
+            .orders
+            .write()
+            .expect("repository lock poisoned");
+        let mut refunds = self
+            .repository
+            .refunds
+            .write()
+            .expect("repository lock poisoned");
+        let mut outbox = self
+            .repository
+            .outbox
+            .write()
+            .expect("repository lock poisoned");
+        let mut transitions = self
+            .repository
+            .transitions
+            .write()
+            .expect("repository lock poisoned");
+
+        // 在副本上应用，全部成功后再替换，保证原子性
+        let mut next_orders = orders.clone();
+        let mut next_refunds = refunds.clone();
+        let mut next_outbox = outbox.clone();
+        let mut next_transitions = transitions.clone();
+        for write in &self.writes {
+            match write {
+                StagedWrite::UpdateState(order) => {
+                    modify_order(&mut next_orders, order.id, |s| apply_state(s, order))?
+                }
+                StagedWrite::SetTransaction(order) => {
+                    modify_order(&mut next_orders, order.id, |s| apply_transaction(s, order))?
+                }
+                StagedWrite::SaveRefund(refund) => insert_refund(&mut next_refunds, refund)?,
+                StagedWrite::UpdateRefund(refund) => modify_refund(&mut next_refunds, refund)?,
+                StagedWrite::SaveOutboxEvent(event) => next_outbox.push(event.clone()),
+                StagedWrite::SaveTransition(transition) => {
+                    next_transitions.push(transition.clone())
+                }
+            }
+        }
+
+        *orders = next_orders;
+        *refunds = next_refunds;
+        *outbox = next_outbox;
+        *transitions = next_transitions;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PaymentRepositoryPort for InMemoryPaymentRepository {
+    type Work = InMemoryUnitOfWork;
+
+    /// 开启工作单元
+    async fn begin(&self) -> DomainResult<InMemoryUnitOfWork> {
+        Ok(InMemoryUnitOfWork {
+            repository: self.clone(),
+            writes: Vec::new(),
+        })
+    }
+
+    /// 保存支付订单
+    async fn save(&self, order: &PaymentOrder) -> DomainResult<()> {
+        let mut orders = self.orders.write().expect("repository lock poisoned");
+
+        if orders
+            .values()
+            .any(|o| o.out_order_no == order.out_order_no)
+        {
+            return Err(DomainError::ValidationError(format!(
+                "Duplicate out order no: {}",
+                order.out_order_no
+            )));
+        }
+
+        orders.insert(order.id, order.clone());
+        debug!("Payment order saved in memory: {}", order.id);
+        Ok(())
+    }
+
+    /// 根据ID查找订单
+    async fn find_by_id(&self, id: uuid::Uuid) -> DomainResult<Option<PaymentOrder>> {
+        let orders = self.orders.read().expect("repository lock poisoned");
+        Ok(orders.get(&id).cloned())
+    }
+
+    /// 根据商户订单号查找
+    async fn find_by_out_order_no(&self, out_order_no: &str) -> DomainResult<Option<PaymentOrder>> {
+        let orders = self.orders.read().expect("repository lock poisoned");
+        Ok(orders
+            .values()
+            .find(|o| o.out_order_no == out_order_no)
+            .cloned())
+    }
+
+    /// 根据微信交易号查找
+    async fn find_by_transaction_id(
+        &self,
+        transaction_id: &str,
+    ) -> DomainResult<Option<PaymentOrder>> {
+        let orders = self.orders.read().expect("repository lock poisoned");
+        Ok(orders
+            .values()
+            .find(|o| o.transaction_id.as_deref() == Some(transaction_id))
+            .cloned())
+    }
+
+    /// 仅更新订单状态
+    async fn update_state(&self, order: &PaymentOrder) -> DomainResult<()> {
+        self.modify(order.id, |stored| apply_state(stored, order))
+    }
+
+    /// 仅更新支付结果
+    async fn set_transaction(&self, order: &PaymentOrder) -> DomainResult<()> {
+        self.modify(order.id, |stored| apply_transaction(stored, order))
+    }
+
+    /// 仅更新预下单结果
+    async fn set_prepay_id(&self, order: &PaymentOrder) -> DomainResult<()> {
+        self.modify(order.id, |stored| {
+            stored.prepay_id = order.prepay_id.clone();
+            stored.code_url = order.code_url.clone();
+            stored.updated_at = order.updated_at;
+        })
+    }
+
+    /// 查询最早的未完成订单创建时间
+    async fn oldest_pending_created_at(
+        &self,
+        created_before: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<Option<chrono::DateTime<chrono::Utc>>> {
+        let orders = self.orders.read().expect("repository lock poisoned");
+        Ok(orders
+            .values()
+            .filter(|o| matches!(o.state, PaymentState::Pending | PaymentState::Processing))
+            .filter(|o| o.created_at < created_before)
+            .map(|o| o.created_at)
+            .min())
+    }
+
+    /// 按时间窗口分页查询未完成订单
+    async fn find_pending_in_window(
+        &self,
+        created_from: chrono::DateTime<chrono::Utc>,
+        created_before: chrono::DateTime<chrono::Utc>,
+        after: Option<PendingCursor>,
+        limit: u32,
+    ) -> DomainResult<Vec<PaymentOrder>> {
+        let orders = self.orders.read().expect("repository lock poisoned");
+        let mut pending: Vec<PaymentOrder> = orders
+            .values()
+            .filter(|o| matches!(o.state, PaymentState::Pending | PaymentState::Processing))
+            .filter(|o| o.created_at >= created_from && o.created_at < created_before)
+            .filter(|o| {
+                after.is_none_or(|cursor| (o.created_at, o.id) > (cursor.created_at, cursor.id))
+            })
+            .cloned()
+            .collect();
+
+        pending.sort_by_key(|o| (o.created_at, o.id));
+        pending.truncate(limit as usize);
+        Ok(pending)
+    }
+
+    /// 分页查询订单
+    async fn list_orders(&self, query: &OrderListQuery) -> DomainResult<OrderPage> {
+        let orders = self.orders.read().expect("repository lock poisoned");
+        let mut sorted: Vec<PaymentOrder> = orders
+            .values()
+            .filter(|o| query.matches(o))
+            .cloned()
+            .collect();
+
+        sorted.sort_by(|a, b| {
+            let ordering = match query.sort {
+                OrderSortField::CreatedAt => a.created_at.cmp(&b.created_at),
+                OrderSortField::UpdatedAt => a.updated_at.cmp(&b.updated_at),
+                OrderSortField::PaidAt => a.paid_at.cmp(&b.paid_at),
+                OrderSortField::Amount => a.amount.to_cents().cmp(&b.amount.to_cents()),
+            };
+            let ordering = match query.direction {
+                SortDirection::Asc => ordering,
+                SortDirection::Desc => ordering.reverse(),
+            };
+            ordering.then_with(|| a.id.cmp(&b.id))
+        });
+
+        let total = sorted.len() as u64;
+        let orders = sorted
+            .into_iter()
+            .skip(query.offset() as usize)
+            .take(query.page_size as usize)
+            .collect();
+
+        Ok(OrderPage { orders, total })
+    }
+
+    /// 流式读取订单（内存实现先按条件取出快照再逐条产出）
+    fn stream_orders(&self, filter: OrderExportFilter) -> OrderStream<'_> {
+        let mut matched: Vec<PaymentOrder> = self
+            .orders
+            .read()
+            .expect("repository lock poisoned")
+            .values()
+            .filter(|o| filter.matches(o))
+            .cloned()
+            .collect();
+        matched.sort_by_key(|o| (o.created_at, o.id));
+
+        Box::pin(futures::stream::iter(matched.into_iter().map(Ok)))
+    }
+
+    /// 删除订单
+    async fn delete(&self, id: uuid::Uuid) -> DomainResult<()> {
+        let mut orders = self.orders.write().expect("repository lock poisoned");
+        orders
+            .remove(&id)
+            .map(|_| ())
+            .ok_or_else(|| DomainError::OrderNotFound(id.to_string()))
+    }
+
+    /// 保存退款记录
+    async fn save_refund(&self, refund: &RefundRecord) -> DomainResult<()> {
+        let mut refunds = self.refunds.write().expect("repository lock poisoned");
+        insert_refund(&mut refunds, refund)
+    }
+
+    /// 更新退款结果
+    async fn update_refund(&self, refund: &RefundRecord) -> DomainResult<()> {
+        let mut refunds = self.refunds.write().expect("repository lock poisoned");
+        modify_refund(&mut refunds, refund)
+    }
+
+    /// 查询订单的全部退款记录
+    async fn find_refunds_by_order(&self, order_id: uuid::Uuid) -> DomainResult<Vec<RefundRecord>> {
+        let refunds = self.refunds.read().expect("repository lock poisoned");
+        Ok(refunds
+            .iter()
+            .filter(|r| r.order_id == order_id)
+            .cloned()
+            .collect())
+    }
+
+    /// 查询订单的全部状态变更记录
+    async fn find_transitions(&self, order_id: uuid::Uuid) -> DomainResult<Vec<StateTransition>> {
+        let transitions = self.transitions.read().expect("repository lock poisoned");
+        Ok(transitions
+            .iter()
+            .filter(|t| t.order_id == order_id)
+            .cloned()
+            .collect())
+    }
+
+    /// 保存回调通知审计记录
+    async fn save_webhook_event(&self, event: &WebhookEvent) -> DomainResult<()> {
+        self.webhook_events
+            .write()
+            .expect("repository lock poisoned")
+            .push(event.clone());
+        Ok(())
+    }
+
+    /// 保存死信通知
+    async fn save_dead_letter(&self, letter: &DeadLetterNotification) -> DomainResult<()> {
+        self.dead_letters
+            .write()
+            .expect("repository lock poisoned")
+            .push(letter.clone());
+        Ok(())
+    }
+
+    /// 查询最近的死信通知
+    async fn list_dead_letters(&self, limit: u32) -> DomainResult<Vec<DeadLetterNotification>> {
+        let letters = self.dead_letters.read().expect("repository lock poisoned");
+        Ok(letters.iter().rev().take(limit as usize).cloned().collect())
+    }
+
+    /// 查询到期待投递的发件箱事件
+    async fn find_due_outbox_events(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+    ) -> DomainResult<Vec<OutboxEvent>> {
+        let outbox = self.outbox.read().expect("repository lock poisoned");
+        let mut due: Vec<OutboxEvent> = outbox
+            .iter()
+            .filter(|e| e.state == OutboxState::Pending && e.next_attempt_at <= now)
+            .cloned()
+            .collect();
+
+        due.sort_by_key(|e| e.created_at);
+        due.truncate(limit as usize);
+        Ok(due)
+    }
+
+    /// 更新发件箱事件的投递结果
+    async fn update_outbox_event(&self, event: &OutboxEvent) -> DomainResult<()> {
+        let mut outbox = self.outbox.write().expect("repository lock poisoned");
+        let stored = outbox
+            .iter_mut()
+            .find(|e| e.id == event.id)
+            .ok_or_else(|| {
+                DomainError::InternalError(format!("Outbox event not found: {}", event.id))
+            })?;
+
+        *stored = event.clone();
+        Ok(())
+    }
+
+    /// 最早一条待投递事件的创建时间
+    async fn oldest_pending_outbox_event(
+        &self,
+    ) -> DomainResult<Option<chrono::DateTime<chrono::Utc>>> {
+        let outbox = self.outbox.read().expect("repository lock poisoned");
+        Ok(outbox
+            .iter()
+            .filter(|e| e.state == OutboxState::Pending)
+            .map(|e| e.created_at)
+            .min())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Money, PaymentMethod};
+
+    fn new_order() -> PaymentOrder {
+        PaymentOrder::new(
+            "1900000109".to_string(),
+            "ORDER123".to_string(),
+            Money::from_yuan(10),
+            PaymentMethod::MiniProgram,
+            "测试商品".to_string(),
+            "127.0.0.1".to_string(),
+            Some("openid123".to_string()),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_update_state_does_not_clobber_prepay_id() {
+        let repository = InMemoryPaymentRepository::new();
+        let order = new_order();
+        repository.save(&order).await.unwrap();
+
+        // 对账任务持有的是设置预下单ID之前读取的旧副本
+        let mut stale = order.clone();
+
+        let mut fresh = order.clone();
+        fresh.set_prepay_id("wx_prepay_123".to_string()).unwrap();
+        repository.set_prepay_id(&fresh).await.unwrap();
+
+        stale.mark_as_closed().unwrap();
+        repository.update_state(&stale).await.unwrap();
+
+        let stored = repository.find_by_id(order.id).await.unwrap().unwrap();
+        assert_eq!(stored.state, PaymentState::Closed);
+        assert_eq!(stored.prepay_id, Some("wx_prepay_123".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_set_transaction_does_not_clobber_prepay_id() {
+        let repository = InMemoryPaymentRepository::new();
+        let mut order = new_order();
+        order.set_prepay_id("wx_prepay_123".to_string()).unwrap();
+        repository.save(&order).await.unwrap();
+
+        let mut stale = order.clone();
+        stale.prepay_id = None;
+        stale.mark_as_succeeded("TX123".to_string()).unwrap();
+        repository.set_transaction(&stale).await.unwrap();
+
+        let stored = repository.find_by_id(order.id).await.unwrap().unwrap();
+        assert_eq!(stored.state, PaymentState::Succeeded);
+        assert_eq!(stored.transaction_id, Some("TX123".to_string()));
+        assert_eq!(stored.prepay_id, Some("wx_prepay_123".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_update_state_missing_order() {
+        let repository = InMemoryPaymentRepository::new();
+        let result = repository.update_state(&new_order()).await;
+        assert!(matches!(result, Err(DomainError::OrderNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_unit_of_work_commit_is_all_or_nothing() {
+        let repository = InMemoryPaymentRepository::new();
+        let mut order = new_order();
+        repository.save(&order).await.unwrap();
+        order.mark_as_succeeded("TX123".to_string()).unwrap();
+        let refund = RefundRecord::new(&order, "RF123".to_string(), order.amount, None).unwrap();
+
+        // 第二个写操作找不到订单，整个工作单元都不应生效
+        let mut work = repository.begin().await.unwrap();
+        work.save_refund(&refund).await.unwrap();
+        work.set_transaction(&new_order()).await.unwrap();
+        assert!(work.commit().await.is_err());
+
+        assert!(
+            repository
+                .find_refunds_by_order(order.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let mut work = repository.begin().await.unwrap();
+        work.save_refund(&refund).await.unwrap();
+        work.set_transaction(&order).await.unwrap();
+        work.commit().await.unwrap();
+
+        assert_eq!(
+            repository
+                .find_refunds_by_order(order.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        let stored = repository.find_by_id(order.id).await.unwrap().unwrap();
+        assert_eq!(stored.state, PaymentState::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_unit_of_work_dropped_without_commit_discards_writes() {
+        let repository = InMemoryPaymentRepository::new();
+        let mut order = new_order();
+        repository.save(&order).await.unwrap();
+        order.mark_as_closed().unwrap();
+
+        let mut work = repository.begin().await.unwrap();
+        work.update_state(&order).await.unwrap();
+        drop(work);
+
+        let stored = repository.find_by_id(order.id).await.unwrap().unwrap();
+        assert_eq!(stored.state, PaymentState::Pending);
+    }
+}
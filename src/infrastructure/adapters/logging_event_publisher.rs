@@ -0,0 +1,26 @@
+use crate::domain::OutboxEvent;
+use crate::domain::errors::DomainResult;
+use crate::ports::EventPublisherPort;
+use async_trait::async_trait;
+use tracing::info;
+
+/// 日志事件发布器（未接入消息队列时使用，仅记录事件）
+#[derive(Clone, Default)]
+pub struct LoggingEventPublisher;
+
+impl LoggingEventPublisher {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl EventPublisherPort for LoggingEventPublisher {
+    async fn publish(&self, event: &OutboxEvent) -> DomainResult<()> {
+        info!(
+            "Published event {} ({}) for {}: {}",
+            event.id, event.event_type, event.aggregate_id, event.payload
+        );
+        Ok(())
+    }
+}
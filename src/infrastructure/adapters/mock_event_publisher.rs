@@ -0,0 +1,71 @@
+use crate::domain::OutboxEvent;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::ports::EventPublisherPort;
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+/// 事件发布模拟器（用于测试，可注入投递失败）
+#[derive(Clone, Default)]
+pub struct MockEventPublisher {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    published: Vec<OutboxEvent>,
+    fail_next: usize,
+    failing_aggregates: HashSet<String>,
+}
+
+impl MockEventPublisher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 让接下来的 n 次发布失败
+    pub fn fail_next(&self, n: usize) {
+        self.state.lock().expect("mock lock poisoned").fail_next = n;
+    }
+
+    /// 让指定聚合的事件始终发布失败
+    pub fn fail_aggregate(&self, aggregate_id: &str) {
+        self.state
+            .lock()
+            .expect("mock lock poisoned")
+            .failing_aggregates
+            .insert(aggregate_id.to_string());
+    }
+
+    /// 已成功发布的事件
+    pub fn published(&self) -> Vec<OutboxEvent> {
+        self.state
+            .lock()
+            .expect("mock lock poisoned")
+            .published
+            .clone()
+    }
+}
+
+#[async_trait]
+impl EventPublisherPort for MockEventPublisher {
+    async fn publish(&self, event: &OutboxEvent) -> DomainResult<()> {
+        let mut state = self.state.lock().expect("mock lock poisoned");
+
+        if state.failing_aggregates.contains(&event.aggregate_id) {
+            return Err(DomainError::InternalError(format!(
+                "Broker rejected event for {}",
+                event.aggregate_id
+            )));
+        }
+        if state.fail_next > 0 {
+            state.fail_next -= 1;
+            return Err(DomainError::ServiceUnavailable(
+                "Broker unavailable".to_string(),
+            ));
+        }
+
+        state.published.push(event.clone());
+        Ok(())
+    }
+}
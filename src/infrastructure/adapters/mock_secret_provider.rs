@@ -0,0 +1,50 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::ports::SecretProvider;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// 密钥提供模拟器（用于测试，记录读取次数）
+#[derive(Clone, Default)]
+pub struct MockSecretProvider {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    secrets: HashMap<String, String>,
+    fetches: usize,
+}
+
+impl MockSecretProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_secret(self, key: &str, value: &str) -> Self {
+        self.state
+            .lock()
+            .expect("mock lock poisoned")
+            .secrets
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// 累计读取次数
+    pub fn fetches(&self) -> usize {
+        self.state.lock().expect("mock lock poisoned").fetches
+    }
+}
+
+#[async_trait]
+impl SecretProvider for MockSecretProvider {
+    async fn get(&self, key: &str) -> DomainResult<String> {
+        let mut state = self.state.lock().expect("mock lock poisoned");
+        state.fetches += 1;
+        state
+            .secrets
+            .get(key)
+            .cloned()
+            .ok_or_else(|| DomainError::ConfigurationError(format!("{} must be set", key)))
+    }
+}
@@ -0,0 +1,229 @@
+use crate::domain::PaymentMethod;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::adapters::wechat_pay_adapter::{api_error, close_outcome, query_error};
+use crate::ports::wechat_pay_port::*;
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+/// 微信支付模拟适配器（用于本地开发和测试，不发起任何网络请求）
+///
+/// 回调解密直接返回密文本身，因此测试中可以把明文JSON放在 `ciphertext` 中。
+#[derive(Clone, Default)]
+pub struct MockWeChatPayAdapter {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    query_response: Option<OrderQueryResponse>,
+    query_error: Option<(u16, String)>,
+    close_error: Option<(u16, String)>,
+    refund_error: Option<(u16, String)>,
+    query_calls: usize,
+    query_delay: Option<std::time::Duration>,
+    queried_orders: Vec<String>,
+    api_v3_keys: Vec<String>,
+    reject_signatures: bool,
+    certificates_unavailable: bool,
+}
+
+impl MockWeChatPayAdapter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置查询订单的返回结果
+    pub fn set_query_response(
+        &self,
+        trade_state: &str,
+        transaction_id: Option<&str>,
+        trade_state_desc: Option<&str>,
+    ) {
+        let mut state = self.state.lock().expect("mock lock poisoned");
+        state.query_response = Some(OrderQueryResponse {
+            trade_state: trade_state.to_string(),
+            transaction_id: transaction_id.map(String::from),
+            trade_state_desc: trade_state_desc.map(String::from),
+        });
+    }
+
+    /// 让查询订单返回微信错误响应（HTTP状态码与响应体）
+    pub fn set_query_error(&self, status: u16, body: &str) {
+        let mut state = self.state.lock().expect("mock lock poisoned");
+        state.query_error = Some((status, body.to_string()));
+    }
+
+    /// 让查询订单延迟返回（模拟微信响应缓慢）
+    pub fn set_query_delay(&self, delay: std::time::Duration) {
+        self.state.lock().expect("mock lock poisoned").query_delay = Some(delay);
+    }
+
+    /// 让关闭订单返回微信错误响应（HTTP状态码与响应体）
+    pub fn set_close_error(&self, status: u16, body: &str) {
+        let mut state = self.state.lock().expect("mock lock poisoned");
+        state.close_error = Some((status, body.to_string()));
+    }
+
+    /// 让申请退款返回微信错误响应（HTTP状态码与响应体）
+    pub fn set_refund_error(&self, status: u16, body: &str) {
+        let mut state = self.state.lock().expect("mock lock poisoned");
+        state.refund_error = Some((status, body.to_string()));
+    }
+
+    /// 恢复申请退款成功
+    pub fn clear_refund_error(&self) {
+        self.state.lock().expect("mock lock poisoned").refund_error = None;
+    }
+
+    /// 让回调验签失败
+    pub fn reject_signatures(&self) {
+        self.state
+            .lock()
+            .expect("mock lock poisoned")
+            .reject_signatures = true;
+    }
+
+    /// 模拟尚未加载平台证书（冷启动），验签返回 `CertificateUnavailable`
+    pub fn unload_certificates(&self) {
+        self.state
+            .lock()
+            .expect("mock lock poisoned")
+            .certificates_unavailable = true;
+    }
+
+    /// 查询订单被调用的次数
+    pub fn query_calls(&self) -> usize {
+        self.state.lock().expect("mock lock poisoned").query_calls
+    }
+
+    /// 按轮换顺序返回推送过的 API v3 密钥
+    pub fn rotated_keys(&self) -> Vec<String> {
+        self.state
+            .lock()
+            .expect("mock lock poisoned")
+            .api_v3_keys
+            .clone()
+    }
+
+    /// 按调用顺序返回被查询过的商户订单号
+    pub fn queried_orders(&self) -> Vec<String> {
+        self.state
+            .lock()
+            .expect("mock lock poisoned")
+            .queried_orders
+            .clone()
+    }
+}
+
+#[async_trait]
+impl WeChatPayPort for MockWeChatPayAdapter {
+    async fn create_mini_program_order(
+        &self,
+        request: WeChatPayRequest,
+    ) -> DomainResult<WeChatPayResponse> {
+        if request.payment_method == PaymentMethod::Native {
+            return Ok(WeChatPayResponse {
+                prepay_id: None,
+                code_url: Some(format!(
+                    "weixin://wxpay/bizpayurl?pr=mock_{}",
+                    request.out_order_no
+                )),
+            });
+        }
+
+        Ok(WeChatPayResponse {
+            prepay_id: Some(format!("wx_mock_{}", request.out_order_no)),
+            code_url: None,
+        })
+    }
+
+    async fn generate_mini_pay_params(
+        &self,
+        prepay_id: &str,
+        _payment_method: PaymentMethod,
+    ) -> DomainResult<MiniProgramPayParams> {
+        Ok(MiniProgramPayParams {
+            time_stamp: "1700000000".to_string(),
+            nonce_str: "mocknonce".to_string(),
+            package: format!("prepay_id={}", prepay_id),
+            sign_type: "RSA".to_string(),
+            pay_sign: "mocksign".to_string(),
+        })
+    }
+
+    async fn query_order(&self, out_order_no: &str) -> DomainResult<OrderQueryResponse> {
+        let delay = self.state.lock().expect("mock lock poisoned").query_delay;
+        if let Some(delay) = delay {
+            tokio::time::sleep(delay).await;
+        }
+
+        let mut state = self.state.lock().expect("mock lock poisoned");
+        state.query_calls += 1;
+        state.queried_orders.push(out_order_no.to_string());
+        if let Some((status, body)) = &state.query_error {
+            return query_error(*status, body);
+        }
+        Ok(state
+            .query_response
+            .clone()
+            .unwrap_or_else(|| OrderQueryResponse {
+                trade_state: "NOTPAY".to_string(),
+                transaction_id: None,
+                trade_state_desc: Some("订单未支付".to_string()),
+            }))
+    }
+
+    async fn close_order(&self, _out_order_no: &str) -> DomainResult<CloseOutcome> {
+        let state = self.state.lock().expect("mock lock poisoned");
+        match &state.close_error {
+            Some((status, body)) => close_outcome(*status, body),
+            None => close_outcome(204, ""),
+        }
+    }
+
+    async fn create_refund(
+        &self,
+        request: WeChatRefundRequest,
+    ) -> DomainResult<WeChatRefundResponse> {
+        if let Some((status, body)) = &self.state.lock().expect("mock lock poisoned").refund_error {
+            return Err(api_error("Create refund failed", *status, body));
+        }
+        Ok(WeChatRefundResponse {
+            refund_id: format!("mock_refund_{}", request.out_refund_no),
+            status: "SUCCESS".to_string(),
+        })
+    }
+
+    async fn verify_notification(
+        &self,
+        serial: &str,
+        _timestamp: &str,
+        _nonce: &str,
+        _body: &str,
+        _signature: &str,
+    ) -> DomainResult<SignatureVerification> {
+        let state = self.state.lock().expect("mock lock poisoned");
+        if state.certificates_unavailable {
+            Err(DomainError::CertificateUnavailable(serial.to_string()))
+        } else if state.reject_signatures {
+            Ok(SignatureVerification::Invalid)
+        } else {
+            Ok(SignatureVerification::Valid)
+        }
+    }
+
+    async fn decrypt_notification(
+        &self,
+        ciphertext: &str,
+        _associated_data: &str,
+        _nonce: &str,
+    ) -> DomainResult<String> {
+        Ok(ciphertext.to_string())
+    }
+
+    async fn rotate_api_v3_key(&self, key: &str) -> DomainResult<()> {
+        let mut state = self.state.lock().expect("mock lock poisoned");
+        state.api_v3_keys.push(key.to_string());
+        Ok(())
+    }
+}
@@ -1,5 +1,29 @@
+pub mod api_v3_key_ring;
+pub mod cached_secret_provider;
+pub mod certificate_manager;
+pub mod clock;
+pub mod env_secret_provider;
+pub mod in_memory_payment_repository;
+pub mod logging_event_publisher;
+pub mod mock_event_publisher;
+pub mod mock_secret_provider;
+pub mod mock_wechat_pay_adapter;
 pub mod mysql_payment_repository;
+pub mod nonce;
+pub mod vault_secret_provider;
 pub mod wechat_pay_adapter;
 
+pub use api_v3_key_ring::ApiV3KeyRing;
+pub use cached_secret_provider::CachedSecretProvider;
+pub use certificate_manager::CertificateManager;
+pub use clock::{FixedClock, SystemClock};
+pub use env_secret_provider::EnvSecretProvider;
+pub use in_memory_payment_repository::InMemoryPaymentRepository;
+pub use logging_event_publisher::LoggingEventPublisher;
+pub use mock_event_publisher::MockEventPublisher;
+pub use mock_secret_provider::MockSecretProvider;
+pub use mock_wechat_pay_adapter::MockWeChatPayAdapter;
 pub use mysql_payment_repository::MySqlPaymentRepository;
+pub use nonce::{FixedNonceGenerator, RandomNonceGenerator};
+pub use vault_secret_provider::VaultSecretProvider;
 pub use wechat_pay_adapter::WeChatPayAdapter;
@@ -1,8 +1,14 @@
 use crate::domain::errors::DomainResult;
-use crate::domain::PaymentOrder;
-use crate::ports::payment_repository_port::PaymentRepositoryPort;
+use crate::domain::{
+    DeadLetterNotification, OutboxEvent, PaymentOrder, RefundRecord, StateTransition, WebhookEvent,
+};
+use crate::ports::payment_repository_port::{
+    OrderExportFilter, OrderListQuery, OrderPage, OrderStream, PaymentRepositoryPort,
+    PendingCursor, UnitOfWork,
+};
 use async_trait::async_trait;
-use sqlx::{MySql, Pool};
+use futures::StreamExt;
+use sqlx::{Executor, MySql, Pool, Transaction};
 use std::sync::Arc;
 use tracing::{debug, error};
 
@@ -16,23 +22,255 @@ impl MySqlPaymentRepository {
     pub fn new(pool: Arc<Pool<MySql>>) -> Self {
         Self { pool }
     }
+
+    /// 仅更新订单状态
+    async fn update_state_with<'e, E>(executor: E, order: &PaymentOrder) -> DomainResult<()>
+    where
+        E: Executor<'e, Database = MySql>,
+    {
+        let query = r#"
+            UPDATE payment_orders
+            SET state = ?, updated_at = ?
+            WHERE id = ?
+        "#;
+
+        let rows_affected = sqlx::query(query)
+            .bind(order.state.to_string())
+            .bind(order.updated_at)
+            .bind(order.id)
+            .execute(executor)
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            error!("No order found to update state: {}", order.id);
+            return Err(crate::domain::errors::DomainError::OrderNotFound(
+                order.id.to_string(),
+            ));
+        }
+
+        debug!(
+            "Payment order state updated: {} -> {}",
+            order.id, order.state
+        );
+        Ok(())
+    }
+
+    /// 仅更新支付结果
+    async fn set_transaction_with<'e, E>(executor: E, order: &PaymentOrder) -> DomainResult<()>
+    where
+        E: Executor<'e, Database = MySql>,
+    {
+        let query = r#"
+            UPDATE payment_orders
+            SET transaction_id = ?, paid_at = ?, state = ?, updated_at = ?
+            WHERE id = ?
+        "#;
+
+        let rows_affected = sqlx::query(query)
+            .bind(&order.transaction_id)
+            .bind(order.paid_at)
+            .bind(order.state.to_string())
+            .bind(order.updated_at)
+            .bind(order.id)
+            .execute(executor)
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            error!("No order found to set transaction: {}", order.id);
+            return Err(crate::domain::errors::DomainError::OrderNotFound(
+                order.id.to_string(),
+            ));
+        }
+
+        debug!("Payment order transaction set: {}", order.id);
+        Ok(())
+    }
+
+    /// 保存退款记录
+    async fn save_refund_with<'e, E>(executor: E, refund: &RefundRecord) -> DomainResult<()>
+    where
+        E: Executor<'e, Database = MySql>,
+    {
+        let query = r#"
+            INSERT INTO payment_refunds (
+                id, order_id, out_order_no, out_refund_no, refund_id,
+                amount_cents, reason, state, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(refund.id)
+            .bind(refund.order_id)
+            .bind(&refund.out_order_no)
+            .bind(&refund.out_refund_no)
+            .bind(&refund.refund_id)
+            .bind(refund.amount.to_cents())
+            .bind(&refund.reason)
+            .bind(refund.state.to_string())
+            .bind(refund.created_at)
+            .bind(refund.updated_at)
+            .execute(executor)
+            .await?;
+
+        debug!("Refund record saved: {}", refund.out_refund_no);
+        Ok(())
+    }
+
+    /// 更新退款结果
+    async fn update_refund_with<'e, E>(executor: E, refund: &RefundRecord) -> DomainResult<()>
+    where
+        E: Executor<'e, Database = MySql>,
+    {
+        let query = r#"
+            UPDATE payment_refunds
+            SET refund_id = ?, state = ?, updated_at = ?
+            WHERE id = ?
+        "#;
+
+        let rows_affected = sqlx::query(query)
+            .bind(&refund.refund_id)
+            .bind(refund.state.to_string())
+            .bind(refund.updated_at)
+            .bind(refund.id)
+            .execute(executor)
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            error!("No refund found to update: {}", refund.out_refund_no);
+            return Err(crate::domain::errors::DomainError::OrderNotFound(
+                refund.out_refund_no.clone(),
+            ));
+        }
+
+        debug!("Refund record updated: {}", refund.out_refund_no);
+        Ok(())
+    }
+
+    /// 写入发件箱事件
+    async fn save_outbox_event_with<'e, E>(executor: E, event: &OutboxEvent) -> DomainResult<()>
+    where
+        E: Executor<'e, Database = MySql>,
+    {
+        let query = r#"
+            INSERT INTO outbox_events (
+                id, event_type, aggregate_id, payload, state, attempts,
+                next_attempt_at, last_error, created_at, published_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(event.id)
+            .bind(&event.event_type)
+            .bind(&event.aggregate_id)
+            .bind(&event.payload)
+            .bind(event.state.to_string())
+            .bind(event.attempts)
+            .bind(event.next_attempt_at)
+            .bind(&event.last_error)
+            .bind(event.created_at)
+            .bind(event.published_at)
+            .execute(executor)
+            .await?;
+
+        debug!("Outbox event saved: {} ({})", event.id, event.event_type);
+        Ok(())
+    }
+
+    /// 写入订单状态变更记录
+    async fn save_transition_with<'e, E>(
+        executor: E,
+        transition: &StateTransition,
+    ) -> DomainResult<()>
+    where
+        E: Executor<'e, Database = MySql>,
+    {
+        let query = r#"
+            INSERT INTO payment_state_transitions (
+                id, order_id, from_state, to_state, created_at
+            ) VALUES (?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(transition.id)
+            .bind(transition.order_id)
+            .bind(transition.from_state.to_string())
+            .bind(transition.to_state.to_string())
+            .bind(transition.created_at)
+            .execute(executor)
+            .await?;
+
+        debug!(
+            "State transition saved: {} {} -> {}",
+            transition.order_id, transition.from_state, transition.to_state
+        );
+        Ok(())
+    }
+}
+
+/// MySQL工作单元（数据库事务，未提交即在释放时回滚）
+pub struct MySqlUnitOfWork {
+    tx: Transaction<'static, MySql>,
+}
+
+#[async_trait]
+impl UnitOfWork for MySqlUnitOfWork {
+    async fn update_state(&mut self, order: &PaymentOrder) -> DomainResult<()> {
+        MySqlPaymentRepository::update_state_with(&mut *self.tx, order).await
+    }
+
+    async fn set_transaction(&mut self, order: &PaymentOrder) -> DomainResult<()> {
+        MySqlPaymentRepository::set_transaction_with(&mut *self.tx, order).await
+    }
+
+    async fn save_refund(&mut self, refund: &RefundRecord) -> DomainResult<()> {
+        MySqlPaymentRepository::save_refund_with(&mut *self.tx, refund).await
+    }
+
+    async fn update_refund(&mut self, refund: &RefundRecord) -> DomainResult<()> {
+        MySqlPaymentRepository::update_refund_with(&mut *self.tx, refund).await
+    }
+
+    async fn save_outbox_event(&mut self, event: &OutboxEvent) -> DomainResult<()> {
+        MySqlPaymentRepository::save_outbox_event_with(&mut *self.tx, event).await
+    }
+
+    async fn save_transition(&mut self, transition: &StateTransition) -> DomainResult<()> {
+        MySqlPaymentRepository::save_transition_with(&mut *self.tx, transition).await
+    }
+
+    async fn commit(self) -> DomainResult<()> {
+        self.tx.commit().await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl PaymentRepositoryPort for MySqlPaymentRepository {
+    type Work = MySqlUnitOfWork;
+
+    /// 开启事务
+    async fn begin(&self) -> DomainResult<MySqlUnitOfWork> {
+        let tx = self.pool.begin().await?;
+        Ok(MySqlUnitOfWork { tx })
+    }
+
     /// 保存支付订单
     async fn save(&self, order: &PaymentOrder) -> DomainResult<()> {
         let query = r#"
             INSERT INTO payment_orders (
-                id, out_order_no, transaction_id, amount_cents,
+                id, merchant_id, out_order_no, transaction_id, amount_cents,
                 payment_method, state, description, openid,
                 client_ip, created_at, updated_at, paid_at,
-                attach, prepay_id
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                attach, prepay_id, parent_out_order_no, goods_tag, code_url
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#;
 
         sqlx::query(query)
             .bind(order.id)
+            .bind(&order.merchant_id)
             .bind(&order.out_order_no)
             .bind(&order.transaction_id)
             .bind(order.amount.to_cents())
@@ -46,6 +284,9 @@ impl PaymentRepositoryPort for MySqlPaymentRepository {
             .bind(order.paid_at)
             .bind(&order.attach)
             .bind(&order.prepay_id)
+            .bind(&order.parent_out_order_no)
+            .bind(&order.goods_tag)
+            .bind(&order.code_url)
             .execute(self.pool.as_ref())
             .await?;
 
@@ -56,10 +297,10 @@ impl PaymentRepositoryPort for MySqlPaymentRepository {
     /// 根据ID查找订单
     async fn find_by_id(&self, id: uuid::Uuid) -> DomainResult<Option<PaymentOrder>> {
         let query = r#"
-            SELECT id, out_order_no, transaction_id, amount_cents,
+            SELECT id, merchant_id, out_order_no, transaction_id, amount_cents,
                    payment_method, state, description, openid,
                    client_ip, created_at, updated_at, paid_at,
-                   attach, prepay_id
+                   attach, prepay_id, parent_out_order_no, goods_tag, code_url
             FROM payment_orders
             WHERE id = ?
         "#;
@@ -75,10 +316,10 @@ impl PaymentRepositoryPort for MySqlPaymentRepository {
     /// 根据商户订单号查找
     async fn find_by_out_order_no(&self, out_order_no: &str) -> DomainResult<Option<PaymentOrder>> {
         let query = r#"
-            SELECT id, out_order_no, transaction_id, amount_cents,
+            SELECT id, merchant_id, out_order_no, transaction_id, amount_cents,
                    payment_method, state, description, openid,
                    client_ip, created_at, updated_at, paid_at,
-                   attach, prepay_id
+                   attach, prepay_id, parent_out_order_no, goods_tag, code_url
             FROM payment_orders
             WHERE out_order_no = ?
         "#;
@@ -97,10 +338,10 @@ impl PaymentRepositoryPort for MySqlPaymentRepository {
         transaction_id: &str,
     ) -> DomainResult<Option<PaymentOrder>> {
         let query = r#"
-            SELECT id, out_order_no, transaction_id, amount_cents,
+            SELECT id, merchant_id, out_order_no, transaction_id, amount_cents,
                    payment_method, state, description, openid,
                    client_ip, created_at, updated_at, paid_at,
-                   attach, prepay_id
+                   attach, prepay_id, parent_out_order_no, goods_tag, code_url
             FROM payment_orders
             WHERE transaction_id = ?
         "#;
@@ -113,36 +354,170 @@ impl PaymentRepositoryPort for MySqlPaymentRepository {
         Ok(result.map(|row| row.into_order()))
     }
 
-    /// 更新订单
-    async fn update(&self, order: &PaymentOrder) -> DomainResult<()> {
+    /// 仅更新订单状态
+    async fn update_state(&self, order: &PaymentOrder) -> DomainResult<()> {
+        Self::update_state_with(self.pool.as_ref(), order).await
+    }
+
+    /// 仅更新支付结果
+    async fn set_transaction(&self, order: &PaymentOrder) -> DomainResult<()> {
+        Self::set_transaction_with(self.pool.as_ref(), order).await
+    }
+
+    /// 仅更新预下单结果
+    async fn set_prepay_id(&self, order: &PaymentOrder) -> DomainResult<()> {
         let query = r#"
             UPDATE payment_orders
-            SET transaction_id = ?, state = ?, updated_at = ?, paid_at = ?, prepay_id = ?
+            SET prepay_id = ?, code_url = ?, updated_at = ?
             WHERE id = ?
         "#;
 
         let rows_affected = sqlx::query(query)
-            .bind(&order.transaction_id)
-            .bind(order.state.to_string())
-            .bind(order.updated_at)
-            .bind(order.paid_at)
             .bind(&order.prepay_id)
+            .bind(&order.code_url)
+            .bind(order.updated_at)
             .bind(order.id)
             .execute(self.pool.as_ref())
             .await?
             .rows_affected();
 
         if rows_affected == 0 {
-            error!("No order found to update: {}", order.id);
+            error!("No order found to set prepay_id: {}", order.id);
             return Err(crate::domain::errors::DomainError::OrderNotFound(
                 order.id.to_string(),
             ));
         }
 
-        debug!("Payment order updated: {}", order.id);
+        debug!("Payment order prepay_id set: {}", order.id);
         Ok(())
     }
 
+    /// 查询最早的未完成订单创建时间
+    async fn oldest_pending_created_at(
+        &self,
+        created_before: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<Option<chrono::DateTime<chrono::Utc>>> {
+        let query = r#"
+            SELECT MIN(created_at) FROM payment_orders
+            WHERE state IN ('pending', 'processing') AND created_at < ?
+        "#;
+
+        let oldest: Option<chrono::DateTime<chrono::Utc>> = sqlx::query_scalar(query)
+            .bind(created_before)
+            .fetch_one(self.pool.as_ref())
+            .await?;
+
+        Ok(oldest)
+    }
+
+    /// 按时间窗口分页查询未完成订单（键集分页，避免 OFFSET 随积压增长变慢）
+    async fn find_pending_in_window(
+        &self,
+        created_from: chrono::DateTime<chrono::Utc>,
+        created_before: chrono::DateTime<chrono::Utc>,
+        after: Option<PendingCursor>,
+        limit: u32,
+    ) -> DomainResult<Vec<PaymentOrder>> {
+        let cursor_clause = if after.is_some() {
+            "AND (created_at > ? OR (created_at = ? AND id > ?))"
+        } else {
+            ""
+        };
+        let sql = format!(
+            r#"
+            SELECT id, merchant_id, out_order_no, transaction_id, amount_cents,
+                   payment_method, state, description, openid,
+                   client_ip, created_at, updated_at, paid_at,
+                   attach, prepay_id, parent_out_order_no, goods_tag, code_url
+            FROM payment_orders
+            WHERE state IN ('pending', 'processing')
+              AND created_at >= ? AND created_at < ?
+              {}
+            ORDER BY created_at ASC, id ASC
+            LIMIT ?
+            "#,
+            cursor_clause
+        );
+
+        let mut query = sqlx::query_as::<_, PaymentOrderRow>(&sql)
+            .bind(created_from)
+            .bind(created_before);
+        if let Some(cursor) = after {
+            query = query
+                .bind(cursor.created_at)
+                .bind(cursor.created_at)
+                .bind(cursor.id);
+        }
+        let rows = query.bind(limit).fetch_all(self.pool.as_ref()).await?;
+
+        Ok(rows.into_iter().map(|row| row.into_order()).collect())
+    }
+
+    /// 分页查询订单
+    async fn list_orders(&self, query: &OrderListQuery) -> DomainResult<OrderPage> {
+        // 排序列与方向均来自白名单枚举，可以安全地拼接进 SQL
+        let sql = format!(
+            r#"
+            SELECT id, merchant_id, out_order_no, transaction_id, amount_cents,
+                   payment_method, state, description, openid,
+                   client_ip, created_at, updated_at, paid_at,
+                   attach, prepay_id, parent_out_order_no, goods_tag, code_url
+            FROM payment_orders
+            WHERE {}
+            ORDER BY {} {}, id ASC
+            LIMIT ? OFFSET ?
+        "#,
+            LIST_FILTER_SQL,
+            query.sort.column(),
+            query.direction.sql()
+        );
+
+        let rows = bind_list_filter(sqlx::query_as::<_, PaymentOrderRow>(&sql), query)
+            .bind(query.page_size)
+            .bind(query.offset())
+            .fetch_all(self.pool.as_ref())
+            .await?;
+
+        let count_sql = format!(
+            "SELECT COUNT(*) FROM payment_orders WHERE {}",
+            LIST_FILTER_SQL
+        );
+        let (total,): (i64,) = bind_list_filter(sqlx::query_as(&count_sql), query)
+            .fetch_one(self.pool.as_ref())
+            .await?;
+
+        Ok(OrderPage {
+            orders: rows.into_iter().map(|row| row.into_order()).collect(),
+            total: total as u64,
+        })
+    }
+
+    /// 流式读取订单：基于 sqlx 的 `fetch` 逐行读取，不在内存中物化结果集
+    fn stream_orders(&self, filter: OrderExportFilter) -> OrderStream<'_> {
+        let query = r#"
+            SELECT id, merchant_id, out_order_no, transaction_id, amount_cents,
+                   payment_method, state, description, openid,
+                   client_ip, created_at, updated_at, paid_at,
+                   attach, prepay_id, parent_out_order_no, goods_tag, code_url
+            FROM payment_orders
+            WHERE (? IS NULL OR merchant_id = ?)
+              AND (? IS NULL OR created_at >= ?)
+              AND (? IS NULL OR created_at < ?)
+            ORDER BY created_at ASC, id ASC
+        "#;
+
+        let rows = sqlx::query_as::<_, PaymentOrderRow>(query)
+            .bind(filter.merchant_id.clone())
+            .bind(filter.merchant_id)
+            .bind(filter.created_from)
+            .bind(filter.created_from)
+            .bind(filter.created_before)
+            .bind(filter.created_before)
+            .fetch(self.pool.as_ref());
+
+        Box::pin(rows.map(|row| Ok(row?.into_order())))
+    }
+
     /// 删除订单（软删除）
     async fn delete(&self, id: uuid::Uuid) -> DomainResult<()> {
         let query = "DELETE FROM payment_orders WHERE id = ?";
@@ -162,12 +537,205 @@ impl PaymentRepositoryPort for MySqlPaymentRepository {
         debug!("Payment order deleted: {}", id);
         Ok(())
     }
+
+    /// 保存退款记录
+    async fn save_refund(&self, refund: &RefundRecord) -> DomainResult<()> {
+        Self::save_refund_with(self.pool.as_ref(), refund).await
+    }
+
+    /// 更新退款结果
+    async fn update_refund(&self, refund: &RefundRecord) -> DomainResult<()> {
+        Self::update_refund_with(self.pool.as_ref(), refund).await
+    }
+
+    /// 查询订单的全部退款记录
+    async fn find_refunds_by_order(&self, order_id: uuid::Uuid) -> DomainResult<Vec<RefundRecord>> {
+        let query = r#"
+            SELECT id, order_id, out_order_no, out_refund_no, refund_id,
+                   amount_cents, reason, state, created_at, updated_at
+            FROM payment_refunds
+            WHERE order_id = ?
+            ORDER BY created_at ASC
+        "#;
+
+        let rows = sqlx::query_as::<_, RefundRecordRow>(query)
+            .bind(order_id)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+
+        Ok(rows.into_iter().map(|row| row.into_refund()).collect())
+    }
+
+    /// 查询订单的全部状态变更记录
+    async fn find_transitions(&self, order_id: uuid::Uuid) -> DomainResult<Vec<StateTransition>> {
+        let query = r#"
+            SELECT id, order_id, from_state, to_state, created_at
+            FROM payment_state_transitions
+            WHERE order_id = ?
+            ORDER BY seq ASC
+        "#;
+
+        let rows = sqlx::query_as::<_, StateTransitionRow>(query)
+            .bind(order_id)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+
+        Ok(rows.into_iter().map(|row| row.into_transition()).collect())
+    }
+
+    /// 保存回调通知审计记录
+    async fn save_webhook_event(&self, event: &WebhookEvent) -> DomainResult<()> {
+        let query = r#"
+            INSERT INTO webhook_events (
+                id, notification_id, event_type, out_order_no, payload, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(event.id)
+            .bind(&event.notification_id)
+            .bind(&event.event_type)
+            .bind(&event.out_order_no)
+            .bind(&event.payload)
+            .bind(event.created_at)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        debug!("Webhook event saved: {}", event.notification_id);
+        Ok(())
+    }
+
+    /// 保存死信通知
+    async fn save_dead_letter(&self, letter: &DeadLetterNotification) -> DomainResult<()> {
+        let query = r#"
+            INSERT INTO dead_letter_notifications (
+                id, notification_id, event_type, reason, raw_body, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(letter.id)
+            .bind(&letter.notification_id)
+            .bind(&letter.event_type)
+            .bind(&letter.reason)
+            .bind(&letter.raw_body)
+            .bind(letter.created_at)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        debug!("Dead letter saved: {}", letter.notification_id);
+        Ok(())
+    }
+
+    /// 查询最近的死信通知
+    async fn list_dead_letters(&self, limit: u32) -> DomainResult<Vec<DeadLetterNotification>> {
+        let query = r#"
+            SELECT id, notification_id, event_type, reason, raw_body, created_at
+            FROM dead_letter_notifications
+            ORDER BY created_at DESC
+            LIMIT ?
+        "#;
+
+        let rows = sqlx::query_as::<_, DeadLetterRow>(query)
+            .bind(limit)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+
+        Ok(rows.into_iter().map(|row| row.into_dead_letter()).collect())
+    }
+
+    /// 查询到期待投递的发件箱事件
+    async fn find_due_outbox_events(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+    ) -> DomainResult<Vec<OutboxEvent>> {
+        let query = r#"
+            SELECT id, event_type, aggregate_id, payload, state, attempts,
+                   next_attempt_at, last_error, created_at, published_at
+            FROM outbox_events
+            WHERE state = 'pending' AND next_attempt_at <= ?
+            ORDER BY created_at ASC
+            LIMIT ?
+        "#;
+
+        let rows = sqlx::query_as::<_, OutboxEventRow>(query)
+            .bind(now)
+            .bind(limit)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+
+        Ok(rows.into_iter().map(|row| row.into_event()).collect())
+    }
+
+    /// 更新发件箱事件的投递结果
+    async fn update_outbox_event(&self, event: &OutboxEvent) -> DomainResult<()> {
+        let query = r#"
+            UPDATE outbox_events
+            SET state = ?, attempts = ?, next_attempt_at = ?, last_error = ?, published_at = ?
+            WHERE id = ?
+        "#;
+
+        sqlx::query(query)
+            .bind(event.state.to_string())
+            .bind(event.attempts)
+            .bind(event.next_attempt_at)
+            .bind(&event.last_error)
+            .bind(event.published_at)
+            .bind(event.id)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        debug!("Outbox event updated: {} -> {}", event.id, event.state);
+        Ok(())
+    }
+
+    /// 最早一条待投递事件的创建时间
+    async fn oldest_pending_outbox_event(
+        &self,
+    ) -> DomainResult<Option<chrono::DateTime<chrono::Utc>>> {
+        let query = "SELECT MIN(created_at) FROM outbox_events WHERE state = 'pending'";
+
+        let oldest: Option<chrono::DateTime<chrono::Utc>> = sqlx::query_scalar(query)
+            .fetch_one(self.pool.as_ref())
+            .await?;
+
+        Ok(oldest)
+    }
+}
+
+/// 订单列表过滤条件（参数顺序与 `bind_list_filter` 一致）
+const LIST_FILTER_SQL: &str = "(? IS NULL OR merchant_id = ?)
+              AND (? IS NULL OR state = ?)
+              AND (? IS NULL OR payment_method = ?)
+              AND (? IS NULL OR created_at >= ?)
+              AND (? IS NULL OR created_at < ?)";
+
+/// 绑定订单列表过滤参数
+fn bind_list_filter<'q, O>(
+    query: sqlx::query::QueryAs<'q, MySql, O, sqlx::mysql::MySqlArguments>,
+    filter: &'q OrderListQuery,
+) -> sqlx::query::QueryAs<'q, MySql, O, sqlx::mysql::MySqlArguments> {
+    let state = filter.state.map(|s| s.to_string());
+    let payment_method = filter.payment_method.map(|m| m.to_string());
+    query
+        .bind(&filter.merchant_id)
+        .bind(&filter.merchant_id)
+        .bind(state.clone())
+        .bind(state)
+        .bind(payment_method.clone())
+        .bind(payment_method)
+        .bind(filter.created_from)
+        .bind(filter.created_from)
+        .bind(filter.created_before)
+        .bind(filter.created_before)
 }
 
 /// 数据库行结构体
 #[derive(Debug, sqlx::FromRow)]
 struct PaymentOrderRow {
     id: uuid::Uuid,
+    merchant_id: String,
     out_order_no: String,
     transaction_id: Option<String>,
     amount_cents: i64,
@@ -181,11 +749,14 @@ struct PaymentOrderRow {
     paid_at: Option<chrono::DateTime<chrono::Utc>>,
     attach: Option<String>,
     prepay_id: Option<String>,
+    parent_out_order_no: Option<String>,
+    goods_tag: Option<String>,
+    code_url: Option<String>,
 }
 
 impl PaymentOrderRow {
     fn into_order(self) -> PaymentOrder {
-        use crate::domain::value_objects::{Money, PaymentMethod, PaymentState};
+        use crate::domain::value_objects::{Money, PaymentMethod};
 
         let payment_method = match self.payment_method.as_str() {
             "mini_program" => PaymentMethod::MiniProgram,
@@ -195,18 +766,11 @@ impl PaymentOrderRow {
             _ => panic!("Invalid payment method: {}", self.payment_method),
         };
 
-        let state = match self.state.as_str() {
-            "pending" => PaymentState::Pending,
-            "processing" => PaymentState::Processing,
-            "succeeded" => PaymentState::Succeeded,
-            "failed" => PaymentState::Failed,
-            "refunded" => PaymentState::Refunded,
-            "closed" => PaymentState::Closed,
-            _ => panic!("Invalid payment state: {}", self.state),
-        };
+        let state = parse_payment_state(&self.state);
 
         PaymentOrder {
             id: self.id,
+            merchant_id: self.merchant_id,
             out_order_no: self.out_order_no,
             transaction_id: self.transaction_id,
             amount: Money::from_cents(self.amount_cents),
@@ -220,6 +784,152 @@ impl PaymentOrderRow {
             paid_at: self.paid_at,
             attach: self.attach,
             prepay_id: self.prepay_id,
+            parent_out_order_no: self.parent_out_order_no,
+            goods_tag: self.goods_tag,
+            code_url: self.code_url,
+        }
+    }
+}
+
+fn parse_payment_state(state: &str) -> crate::domain::PaymentState {
+    use crate::domain::PaymentState;
+
+    match state {
+        "pending" => PaymentState::Pending,
+        "processing" => PaymentState::Processing,
+        "succeeded" => PaymentState::Succeeded,
+        "failed" => PaymentState::Failed,
+        "refunded" => PaymentState::Refunded,
+        "closed" => PaymentState::Closed,
+        _ => panic!("Invalid payment state: {}", state),
+    }
+}
+
+/// 订单状态变更记录行结构体
+#[derive(Debug, sqlx::FromRow)]
+struct StateTransitionRow {
+    id: uuid::Uuid,
+    order_id: uuid::Uuid,
+    from_state: String,
+    to_state: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl StateTransitionRow {
+    fn into_transition(self) -> StateTransition {
+        StateTransition {
+            id: self.id,
+            order_id: self.order_id,
+            from_state: parse_payment_state(&self.from_state),
+            to_state: parse_payment_state(&self.to_state),
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// 退款记录行结构体
+#[derive(Debug, sqlx::FromRow)]
+struct RefundRecordRow {
+    id: uuid::Uuid,
+    order_id: uuid::Uuid,
+    out_order_no: String,
+    out_refund_no: String,
+    refund_id: Option<String>,
+    amount_cents: i64,
+    reason: Option<String>,
+    state: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RefundRecordRow {
+    fn into_refund(self) -> RefundRecord {
+        use crate::domain::value_objects::{Money, RefundState};
+
+        let state = match self.state.as_str() {
+            "processing" => RefundState::Processing,
+            "succeeded" => RefundState::Succeeded,
+            "closed" => RefundState::Closed,
+            "abnormal" => RefundState::Abnormal,
+            _ => panic!("Invalid refund state: {}", self.state),
+        };
+
+        RefundRecord {
+            id: self.id,
+            order_id: self.order_id,
+            out_order_no: self.out_order_no,
+            out_refund_no: self.out_refund_no,
+            refund_id: self.refund_id,
+            amount: Money::from_cents(self.amount_cents),
+            reason: self.reason,
+            state,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+/// 死信通知行结构体
+#[derive(Debug, sqlx::FromRow)]
+struct DeadLetterRow {
+    id: uuid::Uuid,
+    notification_id: String,
+    event_type: String,
+    reason: String,
+    raw_body: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl DeadLetterRow {
+    fn into_dead_letter(self) -> DeadLetterNotification {
+        DeadLetterNotification {
+            id: self.id,
+            notification_id: self.notification_id,
+            event_type: self.event_type,
+            reason: self.reason,
+            raw_body: self.raw_body,
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// 发件箱事件行结构体
+#[derive(Debug, sqlx::FromRow)]
+struct OutboxEventRow {
+    id: uuid::Uuid,
+    event_type: String,
+    aggregate_id: String,
+    payload: String,
+    state: String,
+    attempts: u32,
+    next_attempt_at: chrono::DateTime<chrono::Utc>,
+    last_error: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    published_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl OutboxEventRow {
+    fn into_event(self) -> OutboxEvent {
+        use crate::domain::value_objects::OutboxState;
+
+        let state = match self.state.as_str() {
+            "pending" => OutboxState::Pending,
+            "published" => OutboxState::Published,
+            "poison" => OutboxState::Poison,
+            _ => panic!("Invalid outbox state: {}", self.state),
+        };
+
+        OutboxEvent {
+            id: self.id,
+            event_type: self.event_type,
+            aggregate_id: self.aggregate_id,
+            payload: self.payload,
+            state,
+            attempts: self.attempts,
+            next_attempt_at: self.next_attempt_at,
+            last_error: self.last_error,
+            created_at: self.created_at,
+            published_at: self.published_at,
         }
     }
 }
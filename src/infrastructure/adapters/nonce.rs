@@ -0,0 +1,27 @@
+use crate::ports::NonceGenerator;
+
+/// 随机串生成器（32 位十六进制字符）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomNonceGenerator;
+
+impl NonceGenerator for RandomNonceGenerator {
+    fn generate(&self) -> String {
+        uuid::Uuid::new_v4().simple().to_string()
+    }
+}
+
+/// 固定随机串（用于测试，使签名结果可复现）
+#[derive(Debug, Clone)]
+pub struct FixedNonceGenerator(String);
+
+impl FixedNonceGenerator {
+    pub fn new(nonce: impl Into<String>) -> Self {
+        Self(nonce.into())
+    }
+}
+
+impl NonceGenerator for FixedNonceGenerator {
+    fn generate(&self) -> String {
+        self.0.clone()
+    }
+}
@@ -0,0 +1,63 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::ports::SecretProvider;
+use async_trait::async_trait;
+
+/// HashiCorp Vault 密钥提供方（KV v2 引擎）
+///
+/// 所有密钥保存在同一路径下，以密钥名为字段名，例如
+/// `vault kv put secret/payment-rs WECHAT_PRIVATE_KEY=@key.pem WECHAT_API_V3_KEY=...`。
+#[derive(Clone)]
+pub struct VaultSecretProvider {
+    client: reqwest::Client,
+    addr: String,
+    token: String,
+    mount: String,
+    path: String,
+}
+
+impl VaultSecretProvider {
+    pub fn new(addr: String, token: String, mount: String, path: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            addr: addr.trim_end_matches('/').to_string(),
+            token,
+            mount,
+            path,
+        }
+    }
+
+    fn url(&self) -> String {
+        format!("{}/v1/{}/data/{}", self.addr, self.mount, self.path)
+    }
+}
+
+#[async_trait]
+impl SecretProvider for VaultSecretProvider {
+    async fn get(&self, key: &str) -> DomainResult<String> {
+        let response = self
+            .client
+            .get(self.url())
+            .header("X-Vault-Token", &self.token)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(DomainError::ConfigurationError(format!(
+                "Vault returned {} for {}/{}",
+                status, self.mount, self.path
+            )));
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        body["data"]["data"][key]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| {
+                DomainError::ConfigurationError(format!(
+                    "Secret {} not found in Vault path {}/{}",
+                    key, self.mount, self.path
+                ))
+            })
+    }
+}
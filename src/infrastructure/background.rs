@@ -0,0 +1,94 @@
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+/// 以固定间隔运行后台任务
+///
+/// 取消信号只在两次执行之间检查：正在执行的一轮任务会完整跑完，
+/// 避免在进程退出过程中留下执行到一半的微信/数据库调用。
+pub async fn run_periodic<F, Fut>(
+    name: &'static str,
+    interval: Duration,
+    token: CancellationToken,
+    mut task: F,
+) where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    info!("Background task {} started (interval {:?})", name, interval);
+
+    loop {
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => break,
+            _ = ticker.tick() => task().await,
+        }
+    }
+
+    info!("Background task {} stopped", name);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cancel_stops_loop_within_one_tick() {
+        let interval = Duration::from_secs(10);
+        let runs = Arc::new(AtomicUsize::new(0));
+        let token = CancellationToken::new();
+
+        let handle = tokio::spawn({
+            let runs = runs.clone();
+            let token = token.clone();
+            run_periodic("test", interval, token, move || {
+                let runs = runs.clone();
+                async move {
+                    runs.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+        });
+
+        tokio::time::sleep(interval * 2 + Duration::from_secs(1)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+
+        token.cancel();
+        tokio::time::timeout(interval, handle)
+            .await
+            .expect("loop did not stop within one tick")
+            .unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cancel_waits_for_running_iteration() {
+        let finished = Arc::new(AtomicUsize::new(0));
+        let token = CancellationToken::new();
+
+        let handle = tokio::spawn({
+            let finished = finished.clone();
+            let token = token.clone();
+            run_periodic("test", Duration::from_secs(60), token, move || {
+                let finished = finished.clone();
+                async move {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    finished.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+        });
+
+        // 第一轮执行中途取消
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        token.cancel();
+        handle.await.unwrap();
+
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,42 @@
+/// 运行环境
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppEnvironment {
+    /// 本地开发
+    Development,
+    /// 预发布/测试
+    Staging,
+    /// 生产
+    Production,
+}
+
+impl AppEnvironment {
+    /// 从 `APP_ENV` 读取运行环境，未设置或无法识别时按生产环境处理
+    pub fn from_env() -> Self {
+        std::env::var("APP_ENV")
+            .map(|value| Self::parse(&value))
+            .unwrap_or(AppEnvironment::Production)
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "development" | "dev" | "local" => AppEnvironment::Development,
+            "staging" | "test" => AppEnvironment::Staging,
+            _ => AppEnvironment::Production,
+        }
+    }
+
+    /// 是否为生产环境
+    pub fn is_production(&self) -> bool {
+        *self == AppEnvironment::Production
+    }
+}
+
+impl std::fmt::Display for AppEnvironment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppEnvironment::Development => write!(f, "development"),
+            AppEnvironment::Staging => write!(f, "staging"),
+            AppEnvironment::Production => write!(f, "production"),
+        }
+    }
+}
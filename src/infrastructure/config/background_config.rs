@@ -0,0 +1,129 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use std::time::Duration;
+
+/// 后台任务配置
+#[derive(Debug, Clone)]
+pub struct BackgroundConfig {
+    /// 对账任务执行间隔（为 None 时不启用对账任务）
+    pub reconcile_interval: Option<Duration>,
+
+    /// 对账分页大小（每次加载到内存的订单数量）
+    pub reconcile_batch_size: u32,
+
+    /// 对账时间窗口，按创建时间逐窗口处理积压订单
+    pub reconcile_window: Duration,
+
+    /// 只对账创建超过该时长的订单，避免与用户正在进行的支付竞争
+    pub reconcile_min_age: Duration,
+
+    /// 发件箱投递间隔（为 None 时不启用投递任务）
+    pub outbox_relay_interval: Option<Duration>,
+
+    /// 启动时修复上次进程中断留下的未完成订单（默认关闭）
+    pub reconcile_on_startup: bool,
+
+    /// 启动修复只处理最近该时长内创建的订单
+    pub startup_reconcile_lookback: Duration,
+
+    /// 启动修复最多处理的订单数
+    pub startup_reconcile_limit: u32,
+}
+
+impl Default for BackgroundConfig {
+    fn default() -> Self {
+        Self {
+            reconcile_interval: Some(Duration::from_secs(60)),
+            reconcile_batch_size: 100,
+            reconcile_window: Duration::from_secs(24 * 60 * 60),
+            reconcile_min_age: Duration::from_secs(5 * 60),
+            outbox_relay_interval: Some(Duration::from_secs(5)),
+            reconcile_on_startup: false,
+            startup_reconcile_lookback: Duration::from_secs(24 * 60 * 60),
+            startup_reconcile_limit: 500,
+        }
+    }
+}
+
+impl BackgroundConfig {
+    /// 从环境变量读取；`RECONCILE_ON_STARTUP` 取值无法解析时返回配置错误，避免误以为已开启
+    pub fn from_env() -> DomainResult<Self> {
+        let default = Self::default();
+        let secs = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+
+        Ok(Self {
+            // 间隔设置为0表示关闭对账任务
+            reconcile_interval: match secs("RECONCILE_INTERVAL_SECS") {
+                Some(0) => None,
+                Some(value) => Some(Duration::from_secs(value)),
+                None => default.reconcile_interval,
+            },
+            reconcile_batch_size: std::env::var("RECONCILE_BATCH_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.reconcile_batch_size),
+            reconcile_window: secs("RECONCILE_WINDOW_SECS")
+                .filter(|&value| value > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(default.reconcile_window),
+            reconcile_min_age: secs("RECONCILE_MIN_AGE_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.reconcile_min_age),
+            outbox_relay_interval: match secs("OUTBOX_RELAY_INTERVAL_SECS") {
+                Some(0) => None,
+                Some(value) => Some(Duration::from_secs(value)),
+                None => default.outbox_relay_interval,
+            },
+            reconcile_on_startup: parse_flag(
+                "RECONCILE_ON_STARTUP",
+                std::env::var("RECONCILE_ON_STARTUP").ok().as_deref(),
+                default.reconcile_on_startup,
+            )?,
+            startup_reconcile_lookback: secs("STARTUP_RECONCILE_LOOKBACK_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.startup_reconcile_lookback),
+            startup_reconcile_limit: std::env::var("STARTUP_RECONCILE_LIMIT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.startup_reconcile_limit),
+        })
+    }
+}
+
+/// 解析 `true` / `false` 开关，未设置或为空时使用默认值
+fn parse_flag(name: &str, value: Option<&str>, default: bool) -> DomainResult<bool> {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        None => Ok(default),
+        Some(value) => value.parse().map_err(|_| {
+            DomainError::ConfigurationError(format!(
+                "Invalid {}: {} (expected true or false)",
+                name, value
+            ))
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_flag() {
+        assert!(!parse_flag("RECONCILE_ON_STARTUP", None, false).unwrap());
+        assert!(!parse_flag("RECONCILE_ON_STARTUP", Some(" "), false).unwrap());
+        assert!(parse_flag("RECONCILE_ON_STARTUP", Some("true"), false).unwrap());
+        assert!(!parse_flag("RECONCILE_ON_STARTUP", Some("false"), true).unwrap());
+    }
+
+    #[test]
+    fn test_parse_flag_rejects_unparseable_values() {
+        for value in ["yes", "1", "on", "TRUE "] {
+            let result = parse_flag("RECONCILE_ON_STARTUP", Some(value), false);
+            assert!(
+                matches!(&result, Err(DomainError::ConfigurationError(msg)) if msg.contains("RECONCILE_ON_STARTUP")),
+                "{}: {:?}",
+                value,
+                result
+            );
+        }
+    }
+}
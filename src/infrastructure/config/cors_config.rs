@@ -0,0 +1,137 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::config::AppEnvironment;
+use axum::http::{HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+/// 浏览器跨域（CORS）配置
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    /// 允许的来源，为空时开发环境放行全部、生产环境全部拒绝
+    pub allowed_origins: Vec<HeaderValue>,
+
+    /// 允许的请求方法
+    pub allowed_methods: Vec<Method>,
+
+    /// 允许的请求头
+    pub allowed_headers: Vec<HeaderName>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: Vec::new(),
+            allowed_methods: vec![Method::GET, Method::POST],
+            allowed_headers: vec![
+                axum::http::header::CONTENT_TYPE,
+                axum::http::header::AUTHORIZATION,
+            ],
+        }
+    }
+}
+
+impl CorsConfig {
+    /// 从 `CORS_ALLOWED_ORIGINS`、`CORS_ALLOWED_METHODS`、`CORS_ALLOWED_HEADERS` 读取（逗号分隔）
+    pub fn from_env() -> DomainResult<Self> {
+        let var = |name: &str| std::env::var(name).ok();
+        Self::parse(
+            var("CORS_ALLOWED_ORIGINS").as_deref(),
+            var("CORS_ALLOWED_METHODS").as_deref(),
+            var("CORS_ALLOWED_HEADERS").as_deref(),
+        )
+    }
+
+    /// 校验并构造配置，未设置的项使用默认值
+    pub fn parse(
+        origins: Option<&str>,
+        methods: Option<&str>,
+        headers: Option<&str>,
+    ) -> DomainResult<Self> {
+        let default = Self::default();
+
+        Ok(Self {
+            allowed_origins: parse_list(origins, "CORS_ALLOWED_ORIGINS", |v| v.parse().ok())?
+                .unwrap_or(default.allowed_origins),
+            allowed_methods: parse_list(methods, "CORS_ALLOWED_METHODS", |v| {
+                v.to_ascii_uppercase().parse().ok()
+            })?
+            .unwrap_or(default.allowed_methods),
+            allowed_headers: parse_list(headers, "CORS_ALLOWED_HEADERS", |v| v.parse().ok())?
+                .unwrap_or(default.allowed_headers),
+        })
+    }
+
+    /// 构建CORS中间件
+    pub fn layer(&self, environment: AppEnvironment) -> CorsLayer {
+        if self.allowed_origins.is_empty() {
+            return if environment.is_production() {
+                // 未配置来源时生产环境拒绝所有跨域请求
+                CorsLayer::new()
+            } else {
+                CorsLayer::permissive()
+            };
+        }
+
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::list(self.allowed_origins.clone()))
+            .allow_methods(self.allowed_methods.clone())
+            .allow_headers(self.allowed_headers.clone())
+    }
+}
+
+/// 解析逗号分隔的列表，未设置或为空时返回 None
+fn parse_list<V>(
+    value: Option<&str>,
+    name: &str,
+    parse: impl Fn(&str) -> Option<V>,
+) -> DomainResult<Option<Vec<V>>> {
+    let Some(value) = value.filter(|v| !v.trim().is_empty()) else {
+        return Ok(None);
+    };
+
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            parse(item).ok_or_else(|| {
+                DomainError::ConfigurationError(format!("Invalid {} entry: {}", name, item))
+            })
+        })
+        .collect::<DomainResult<Vec<V>>>()
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lists() {
+        let config = CorsConfig::parse(
+            Some("https://shop.example.com, https://m.example.com"),
+            Some("get,post"),
+            Some("Content-Type"),
+        )
+        .unwrap();
+
+        assert_eq!(config.allowed_origins.len(), 2);
+        assert_eq!(config.allowed_methods, vec![Method::GET, Method::POST]);
+        assert_eq!(
+            config.allowed_headers,
+            vec![axum::http::header::CONTENT_TYPE]
+        );
+    }
+
+    #[test]
+    fn test_defaults_when_unset() {
+        let config = CorsConfig::parse(None, Some(""), None).unwrap();
+        assert!(config.allowed_origins.is_empty());
+        assert_eq!(config.allowed_methods, vec![Method::GET, Method::POST]);
+    }
+
+    #[test]
+    fn test_rejects_invalid_entries() {
+        let result = CorsConfig::parse(None, None, Some("bad header"));
+        assert!(matches!(result, Err(DomainError::ConfigurationError(_))));
+    }
+}
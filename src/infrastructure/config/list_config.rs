@@ -0,0 +1,99 @@
+use crate::domain::errors::{DomainError, DomainResult};
+
+/// 列表接口分页配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListConfig {
+    /// 未指定 `page_size` 时的每页数量
+    pub default_page_size: u32,
+
+    /// 每页数量上限，超出的请求被截断到该值
+    pub max_page_size: u32,
+}
+
+impl Default for ListConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 20,
+            max_page_size: 100,
+        }
+    }
+}
+
+impl ListConfig {
+    /// 从 `LIST_DEFAULT_PAGE_SIZE`、`LIST_MAX_PAGE_SIZE` 读取
+    pub fn from_env() -> DomainResult<Self> {
+        let var = |name: &str| std::env::var(name).ok();
+        Self::parse(
+            var("LIST_DEFAULT_PAGE_SIZE").as_deref(),
+            var("LIST_MAX_PAGE_SIZE").as_deref(),
+        )
+    }
+
+    /// 校验并构造配置，未设置的项使用默认值；默认值不能超过上限
+    pub fn parse(
+        default_page_size: Option<&str>,
+        max_page_size: Option<&str>,
+    ) -> DomainResult<Self> {
+        let default = Self::default();
+        let size = |value: Option<&str>, name: &str, fallback: u32| -> DomainResult<u32> {
+            match value.map(str::trim).filter(|v| !v.is_empty()) {
+                None => Ok(fallback),
+                Some(value) => match value.parse::<u32>() {
+                    Ok(size) if size > 0 => Ok(size),
+                    _ => Err(DomainError::ConfigurationError(format!(
+                        "Invalid {}: {} (expected a positive integer)",
+                        name, value
+                    ))),
+                },
+            }
+        };
+
+        let config = Self {
+            default_page_size: size(
+                default_page_size,
+                "LIST_DEFAULT_PAGE_SIZE",
+                default.default_page_size,
+            )?,
+            max_page_size: size(max_page_size, "LIST_MAX_PAGE_SIZE", default.max_page_size)?,
+        };
+
+        if config.default_page_size > config.max_page_size {
+            return Err(DomainError::ConfigurationError(format!(
+                "LIST_DEFAULT_PAGE_SIZE ({}) must not exceed LIST_MAX_PAGE_SIZE ({})",
+                config.default_page_size, config.max_page_size
+            )));
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sizes() {
+        assert_eq!(
+            ListConfig::parse(None, Some("")).unwrap(),
+            ListConfig::default()
+        );
+
+        let config = ListConfig::parse(Some("50"), Some("500")).unwrap();
+        assert_eq!(config.default_page_size, 50);
+        assert_eq!(config.max_page_size, 500);
+    }
+
+    #[test]
+    fn test_rejects_invalid_sizes() {
+        for (default_size, max_size) in [(Some("0"), None), (None, Some("-1")), (Some("ten"), None)]
+        {
+            let result = ListConfig::parse(default_size, max_size);
+            assert!(matches!(result, Err(DomainError::ConfigurationError(_))));
+        }
+
+        // 默认值超过上限
+        let result = ListConfig::parse(Some("200"), None);
+        assert!(matches!(result, Err(DomainError::ConfigurationError(_))));
+    }
+}
@@ -1,3 +1,17 @@
+pub mod app_config;
+pub mod background_config;
+pub mod cors_config;
+pub mod list_config;
+pub mod secrets_config;
+pub mod server_config;
+pub mod timeout_config;
 pub mod wechat_config;
 
+pub use app_config::AppEnvironment;
+pub use background_config::BackgroundConfig;
+pub use cors_config::CorsConfig;
+pub use list_config::ListConfig;
+pub use secrets_config::{SecretBackend, SecretsConfig};
+pub use server_config::{ServerConfig, parse_envelope_flag};
+pub use timeout_config::TimeoutConfig;
 pub use wechat_config::WeChatPayConfig;
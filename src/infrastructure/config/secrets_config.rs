@@ -0,0 +1,89 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::adapters::{
+    CachedSecretProvider, EnvSecretProvider, VaultSecretProvider,
+};
+use crate::ports::SecretProvider;
+use std::sync::Arc;
+
+/// 密钥来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretBackend {
+    /// 环境变量（默认）
+    Env,
+    /// HashiCorp Vault KV v2
+    Vault {
+        addr: String,
+        token: String,
+        mount: String,
+        path: String,
+    },
+}
+
+/// 密钥提供方配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretsConfig {
+    pub backend: SecretBackend,
+
+    /// 密钥缓存时间
+    pub cache_ttl: chrono::Duration,
+}
+
+impl SecretsConfig {
+    /// 从 `SECRET_PROVIDER`（`env` / `vault`）、`SECRET_CACHE_TTL_SECS` 与 `VAULT_*` 读取
+    pub fn from_env() -> DomainResult<Self> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .map_err(|_| DomainError::ConfigurationError(format!("{} must be set", name)))
+        };
+
+        let backend = match std::env::var("SECRET_PROVIDER")
+            .unwrap_or_else(|_| "env".to_string())
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "env" => SecretBackend::Env,
+            "vault" => SecretBackend::Vault {
+                addr: var("VAULT_ADDR")?,
+                token: var("VAULT_TOKEN")?,
+                mount: std::env::var("VAULT_MOUNT").unwrap_or_else(|_| "secret".to_string()),
+                path: var("VAULT_SECRET_PATH")?,
+            },
+            other => {
+                return Err(DomainError::ConfigurationError(format!(
+                    "Invalid SECRET_PROVIDER: {} (expected env or vault)",
+                    other
+                )));
+            }
+        };
+
+        let cache_ttl = std::env::var("SECRET_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(300);
+
+        Ok(Self {
+            backend,
+            cache_ttl: chrono::Duration::seconds(cache_ttl),
+        })
+    }
+
+    /// 构建带缓存的密钥提供方
+    pub fn provider(&self) -> Arc<dyn SecretProvider> {
+        let inner: Arc<dyn SecretProvider> = match &self.backend {
+            SecretBackend::Env => Arc::new(EnvSecretProvider::new()),
+            SecretBackend::Vault {
+                addr,
+                token,
+                mount,
+                path,
+            } => Arc::new(VaultSecretProvider::new(
+                addr.clone(),
+                token.clone(),
+                mount.clone(),
+                path.clone(),
+            )),
+        };
+        Arc::new(CachedSecretProvider::new(inner, self.cache_ttl))
+    }
+}
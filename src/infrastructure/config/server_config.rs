@@ -0,0 +1,167 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+/// HTTP服务配置
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// 监听地址
+    pub host: IpAddr,
+
+    /// 监听端口
+    pub port: u16,
+
+    /// 管理接口令牌（为 None 时管理接口不可用）
+    pub admin_token: Option<String>,
+
+    /// 是否默认包装成功响应
+    pub response_envelope: bool,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            port: 3000,
+            admin_token: None,
+            response_envelope: false,
+        }
+    }
+}
+
+impl ServerConfig {
+    /// 从 `SERVER_HOST`、`SERVER_PORT`、`ADMIN_TOKEN`、`RESPONSE_ENVELOPE` 读取配置
+    pub fn from_env() -> DomainResult<Self> {
+        let var = |name: &str| std::env::var(name).ok();
+        let config = Self::parse(
+            var("SERVER_HOST").as_deref(),
+            var("SERVER_PORT").as_deref(),
+            var("ADMIN_TOKEN"),
+        )?;
+
+        let response_envelope = match var("RESPONSE_ENVELOPE")
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+        {
+            Some(value) => parse_envelope_flag(value).ok_or_else(|| {
+                DomainError::ConfigurationError(format!(
+                    "Invalid RESPONSE_ENVELOPE: {} (expected true, false, 1 or 0)",
+                    value
+                ))
+            })?,
+            None => config.response_envelope,
+        };
+
+        Ok(Self {
+            response_envelope,
+            ..config
+        })
+    }
+
+    /// 校验并构造配置，未设置的项使用默认值
+    pub fn parse(
+        host: Option<&str>,
+        port: Option<&str>,
+        admin_token: Option<String>,
+    ) -> DomainResult<Self> {
+        let default = Self::default();
+
+        let host = match host.map(str::trim).filter(|h| !h.is_empty()) {
+            Some(value) => value.parse().map_err(|_| {
+                DomainError::ConfigurationError(format!("Invalid SERVER_HOST: {}", value))
+            })?,
+            None => default.host,
+        };
+
+        let port = match port.map(str::trim).filter(|p| !p.is_empty()) {
+            Some(value) => match value.parse::<u16>() {
+                Ok(port) if port > 0 => port,
+                _ => {
+                    return Err(DomainError::ConfigurationError(format!(
+                        "Invalid SERVER_PORT: {} (expected 1-65535)",
+                        value
+                    )));
+                }
+            },
+            None => default.port,
+        };
+
+        Ok(Self {
+            host,
+            port,
+            admin_token: admin_token.filter(|t| !t.is_empty()),
+            response_envelope: default.response_envelope,
+        })
+    }
+
+    /// 监听的套接字地址
+    pub fn socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.host, self.port)
+    }
+}
+
+/// 解析响应包装开关（`RESPONSE_ENVELOPE` 与 `X-Response-Envelope` 共用）：
+/// 接受 `true` / `false` / `1` / `0`（不区分大小写），其他值返回 None
+pub fn parse_envelope_flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" => Some(true),
+        "0" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_envelope_flag() {
+        for value in ["true", "TRUE", " 1 "] {
+            assert_eq!(parse_envelope_flag(value), Some(true), "{}", value);
+        }
+        for value in ["false", "False", "0"] {
+            assert_eq!(parse_envelope_flag(value), Some(false), "{}", value);
+        }
+        for value in ["yes", "on", "2", ""] {
+            assert_eq!(parse_envelope_flag(value), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_defaults_when_unset() {
+        let config = ServerConfig::parse(None, None, None).unwrap();
+        assert_eq!(config.socket_addr().to_string(), "0.0.0.0:3000");
+        assert!(config.admin_token.is_none());
+
+        let config = ServerConfig::parse(Some(""), Some(" "), Some(String::new())).unwrap();
+        assert_eq!(config.port, 3000);
+        assert!(config.admin_token.is_none());
+    }
+
+    #[test]
+    fn test_parses_host_and_port() {
+        let config = ServerConfig::parse(Some("127.0.0.1"), Some("8080"), None).unwrap();
+        assert_eq!(config.socket_addr().to_string(), "127.0.0.1:8080");
+
+        let config = ServerConfig::parse(Some("::1"), Some("65535"), None).unwrap();
+        assert_eq!(config.socket_addr().to_string(), "[::1]:65535");
+    }
+
+    #[test]
+    fn test_rejects_invalid_port() {
+        for port in ["0", "65536", "-1", "http"] {
+            let result = ServerConfig::parse(None, Some(port), None);
+            assert!(
+                matches!(result, Err(DomainError::ConfigurationError(_))),
+                "port {} should be rejected",
+                port
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_host() {
+        let result = ServerConfig::parse(Some("not a host"), None, None);
+        assert!(matches!(result, Err(DomainError::ConfigurationError(_))));
+    }
+}
@@ -0,0 +1,64 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use axum::http::StatusCode;
+use std::time::Duration;
+use tower_http::timeout::TimeoutLayer;
+
+/// 接口超时配置
+///
+/// 超时后返回 504，处理中的 future 被直接丢弃：未提交的事务随之回滚，进行中的微信请求被取消。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutConfig {
+    /// 创建支付订单
+    pub create: Duration,
+
+    /// 查询订单（可能同步请求微信）
+    pub query: Duration,
+
+    /// 微信支付回调
+    pub webhook: Duration,
+
+    /// 其他接口（列表、退款、关闭、管理接口）
+    pub default: Duration,
+}
+
+impl Default for TimeoutConfig {
+    fn default() -> Self {
+        Self {
+            create: Duration::from_secs(15),
+            query: Duration::from_secs(10),
+            webhook: Duration::from_secs(20),
+            default: Duration::from_secs(30),
+        }
+    }
+}
+
+impl TimeoutConfig {
+    /// 从 `TIMEOUT_CREATE_SECS`、`TIMEOUT_QUERY_SECS`、`TIMEOUT_WEBHOOK_SECS`、`TIMEOUT_DEFAULT_SECS` 读取
+    pub fn from_env() -> DomainResult<Self> {
+        let default = Self::default();
+        let secs = |name: &str, fallback: Duration| -> DomainResult<Duration> {
+            match std::env::var(name).ok().map(|v| v.trim().to_string()) {
+                None => Ok(fallback),
+                Some(value) => match value.parse::<u64>() {
+                    Ok(secs) if secs > 0 => Ok(Duration::from_secs(secs)),
+                    _ => Err(DomainError::ConfigurationError(format!(
+                        "Invalid {}: {} (expected a positive number of seconds)",
+                        name, value
+                    ))),
+                },
+            }
+        };
+
+        Ok(Self {
+            create: secs("TIMEOUT_CREATE_SECS", default.create)?,
+            query: secs("TIMEOUT_QUERY_SECS", default.query)?,
+            webhook: secs("TIMEOUT_WEBHOOK_SECS", default.webhook)?,
+            default: secs("TIMEOUT_DEFAULT_SECS", default.default)?,
+        })
+    }
+
+    /// 超时返回 504 的 tower 层
+    pub fn layer(timeout: Duration) -> TimeoutLayer {
+        TimeoutLayer::with_status_code(StatusCode::GATEWAY_TIMEOUT, timeout)
+    }
+}
@@ -1,3 +1,6 @@
+use crate::domain::PaymentMethod;
+use crate::domain::errors::DomainResult;
+use crate::ports::SecretProvider;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
@@ -19,30 +22,96 @@ pub struct WeChatPayConfig {
     /// 商户API v3密钥（用于回调通知解密）
     pub api_v3_key: String,
 
-    /// APPID
-    pub appid: String,
+    /// 轮换过渡期内仍需支持解密的旧 API v3 密钥（从新到旧）
+    pub previous_api_v3_keys: Vec<String>,
+
+    /// 小程序 APPID（小程序支付使用）
+    pub miniprogram_appid: String,
+
+    /// 公众号 APPID（JSAPI 支付使用）
+    pub jsapi_appid: String,
 
     /// API基础URL
     pub base_url: String,
+
+    /// 微信支付平台公钥ID（回调验签使用）
+    pub platform_public_key_id: Option<String>,
+
+    /// 微信支付平台公钥内容（PEM格式）
+    pub platform_public_key: Option<String>,
+
+    /// 允许的回调签名序列号，为空时接受所有已加载的平台证书
+    pub accepted_serials: Vec<String>,
 }
 
+/// 商户私钥的密钥名
+pub const PRIVATE_KEY_SECRET: &str = "WECHAT_PRIVATE_KEY";
+
+/// API v3 密钥的密钥名
+pub const API_V3_KEY_SECRET: &str = "WECHAT_API_V3_KEY";
+
 impl WeChatPayConfig {
-    pub fn from_env() -> Arc<Self> {
-        Arc::new(Self {
+    /// 从环境变量读取非敏感配置，商户私钥与 API v3 密钥通过 `secrets` 获取
+    pub async fn load(secrets: &dyn SecretProvider) -> DomainResult<Arc<Self>> {
+        let mut config = Self::from_env_without_secrets();
+        config.apply_secrets(secrets).await?;
+        Ok(Arc::new(config))
+    }
+
+    /// 按支付方式选择 APPID：小程序与公众号的 APPID 不同，用错时微信返回 APPID_MCHID_NOT_MATCH
+    pub fn appid_for(&self, payment_method: PaymentMethod) -> &str {
+        match payment_method {
+            PaymentMethod::MiniProgram => &self.miniprogram_appid,
+            PaymentMethod::Jsapi | PaymentMethod::Native | PaymentMethod::H5 => &self.jsapi_appid,
+        }
+    }
+
+    /// 通过密钥提供方填充商户私钥与 API v3 密钥
+    pub async fn apply_secrets(&mut self, secrets: &dyn SecretProvider) -> DomainResult<()> {
+        self.private_key = secrets.get(PRIVATE_KEY_SECRET).await?;
+        self.api_v3_key = secrets.get(API_V3_KEY_SECRET).await?;
+        Ok(())
+    }
+
+    fn from_env_without_secrets() -> Self {
+        // 未单独配置时两者都回退到 WECHAT_APPID
+        let appid = |name: &str| {
+            std::env::var(name)
+                .or_else(|_| std::env::var("WECHAT_APPID"))
+                .unwrap_or_else(|_| panic!("{} or WECHAT_APPID must be set", name))
+        };
+
+        Self {
             mchid: std::env::var("WECHAT_MCHID")
                 .expect("WECHAT_MCHID must be set"),
             serial_no: std::env::var("WECHAT_SERIAL_NO")
                 .expect("WECHAT_SERIAL_NO must be set"),
             private_key_path: std::env::var("WECHAT_PRIVATE_KEY_PATH")
                 .unwrap_or_else(|_| String::new()),
-            private_key: std::env::var("WECHAT_PRIVATE_KEY")
-                .expect("WECHAT_PRIVATE_KEY must be set"),
-            api_v3_key: std::env::var("WECHAT_API_V3_KEY")
-                .expect("WECHAT_API_V3_KEY must be set"),
-            appid: std::env::var("WECHAT_APPID")
-                .expect("WECHAT_APPID must be set"),
+            private_key: String::new(),
+            api_v3_key: String::new(),
+            previous_api_v3_keys: std::env::var("WECHAT_API_V3_PREVIOUS_KEYS")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            miniprogram_appid: appid("WECHAT_MINIPROGRAM_APPID"),
+            jsapi_appid: appid("WECHAT_JSAPI_APPID"),
             base_url: std::env::var("WECHAT_BASE_URL")
                 .unwrap_or_else(|_| "https://api.mch.weixin.qq.com".to_string()),
-        })
+            platform_public_key_id: std::env::var("WECHAT_PLATFORM_PUBLIC_KEY_ID").ok(),
+            platform_public_key: std::env::var("WECHAT_PLATFORM_PUBLIC_KEY").ok(),
+            accepted_serials: std::env::var("WECHAT_ACCEPTED_SERIALS")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
     }
 }
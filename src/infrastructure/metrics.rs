@@ -0,0 +1,119 @@
+use prometheus::{Encoder, Gauge, IntCounterVec, Opts, Registry, TextEncoder};
+use std::time::Duration;
+
+/// 服务指标（Prometheus 文本格式，通过 `/metrics` 暴露）
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    outbox_relay_lag_seconds: Gauge,
+    outbox_relay_events_total: IntCounterVec,
+    webhook_signature_verifications_total: IntCounterVec,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let outbox_relay_lag_seconds = Gauge::with_opts(Opts::new(
+            "outbox_relay_lag_seconds",
+            "Age of the oldest unpublished outbox event in seconds",
+        ))
+        .expect("valid metric");
+        let outbox_relay_events_total = IntCounterVec::new(
+            Opts::new(
+                "outbox_relay_events_total",
+                "Outbox events processed by the relay",
+            ),
+            &["result"],
+        )
+        .expect("valid metric");
+        let webhook_signature_verifications_total = IntCounterVec::new(
+            Opts::new(
+                "webhook_signature_verifications_total",
+                "Webhook signature verification outcomes",
+            ),
+            &["result"],
+        )
+        .expect("valid metric");
+
+        registry
+            .register(Box::new(outbox_relay_lag_seconds.clone()))
+            .expect("metric registered once");
+        registry
+            .register(Box::new(outbox_relay_events_total.clone()))
+            .expect("metric registered once");
+        registry
+            .register(Box::new(webhook_signature_verifications_total.clone()))
+            .expect("metric registered once");
+
+        Self {
+            registry,
+            outbox_relay_lag_seconds,
+            outbox_relay_events_total,
+            webhook_signature_verifications_total,
+        }
+    }
+
+    /// 记录发件箱投递延迟（无待投递事件时为0）
+    pub fn set_outbox_lag(&self, lag: Option<Duration>) {
+        self.outbox_relay_lag_seconds
+            .set(lag.map(|d| d.as_secs_f64()).unwrap_or(0.0));
+    }
+
+    /// 记录发件箱投递结果（published / retried / poisoned）
+    pub fn record_outbox_events(&self, result: &str, count: usize) {
+        self.outbox_relay_events_total
+            .with_label_values(&[result])
+            .inc_by(count as u64);
+    }
+
+    /// 记录回调签名校验结果（ok / invalid / stale / missing）
+    pub fn record_signature_verification(&self, result: &str) {
+        self.webhook_signature_verifications_total
+            .with_label_values(&[result])
+            .inc();
+    }
+
+    /// 以 Prometheus 文本格式输出全部指标
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("text encoding never fails");
+        String::from_utf8(buffer).expect("metrics are valid UTF-8")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_outbox_metrics() {
+        let metrics = Metrics::new();
+        metrics.set_outbox_lag(Some(Duration::from_secs(12)));
+        metrics.record_outbox_events("poisoned", 2);
+
+        let output = metrics.render();
+        assert!(output.contains("outbox_relay_lag_seconds 12"));
+        assert!(output.contains(r#"outbox_relay_events_total{result="poisoned"} 2"#));
+    }
+
+    #[test]
+    fn test_render_signature_verification_counter() {
+        let metrics = Metrics::new();
+        metrics.record_signature_verification("invalid");
+        metrics.record_signature_verification("invalid");
+        metrics.record_signature_verification("ok");
+
+        let output = metrics.render();
+        assert!(output.contains(r#"webhook_signature_verifications_total{result="invalid"} 2"#));
+        assert!(output.contains(r#"webhook_signature_verifications_total{result="ok"} 1"#));
+    }
+}
@@ -1,5 +1,8 @@
 pub mod adapters;
+pub mod background;
 pub mod config;
+pub mod metrics;
 
 pub use adapters::*;
 pub use config::*;
+pub use metrics::Metrics;
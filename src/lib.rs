@@ -0,0 +1,5 @@
+pub mod api;
+pub mod application;
+pub mod domain;
+pub mod infrastructure;
+pub mod ports;
@@ -1,16 +1,17 @@
-mod api;
-mod application;
-mod domain;
-mod infrastructure;
-mod ports;
-
-use api::AppState;
-use application::PaymentService;
-use infrastructure::{MySqlPaymentRepository, WeChatPayAdapter, WeChatPayConfig};
+use payment_rs::api::{self, AppState};
+use payment_rs::application::{
+    AmountGuard, OutboxRelay, OutboxRelayConfig, PaymentService, PaymentServiceConfig,
+};
+use payment_rs::infrastructure::background::run_periodic;
+use payment_rs::infrastructure::{
+    AppEnvironment, BackgroundConfig, CorsConfig, ListConfig, LoggingEventPublisher, Metrics,
+    MySqlPaymentRepository, SecretsConfig, ServerConfig, TimeoutConfig, WeChatPayAdapter,
+    WeChatPayConfig,
+};
 use sqlx::MySqlPool;
 use std::sync::Arc;
-use tracing::{info, Level};
-use tracing_subscriber::{fmt, EnvFilter};
+use tokio_util::sync::CancellationToken;
+use tracing::{Level, error, info};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -23,7 +24,10 @@ async fn main() -> anyhow::Result<()> {
     // 加载环境变量
     dotenvy::dotenv().ok();
 
-    info!("Starting Payment Service...");
+    let environment = AppEnvironment::from_env();
+    info!("Starting Payment Service ({})...", environment);
+
+    let server_config = ServerConfig::from_env()?;
 
     // 创建数据库连接池
     let database_url = std::env::var("DATABASE_URL")
@@ -33,8 +37,9 @@ async fn main() -> anyhow::Result<()> {
     let pool = MySqlPool::connect(&database_url).await?;
     info!("Database connected successfully");
 
-    // 初始化微信支付配置
-    let wechat_config = WeChatPayConfig::from_env();
+    // 初始化微信支付配置（商户私钥与 API v3 密钥通过密钥提供方获取）
+    let secrets = SecretsConfig::from_env()?.provider();
+    let wechat_config = WeChatPayConfig::load(secrets.as_ref()).await?;
     info!("WeChat Pay configuration loaded for mchid: {}", wechat_config.mchid);
 
     // 创建微信支付适配器
@@ -44,36 +49,187 @@ async fn main() -> anyhow::Result<()> {
     let repository = Arc::new(MySqlPaymentRepository::new(Arc::new(pool)));
 
     // 创建支付服务
-    let payment_service = Arc::new(PaymentService::new(
-        wechat_adapter,
-        repository,
-    ));
+    let mut service_config = PaymentServiceConfig::from_env();
+    // 大额保护仅在非生产环境启用
+    service_config.amount_guard = (!environment.is_production()).then(AmountGuard::from_env);
+    service_config.default_merchant_id = Some(wechat_config.mchid.clone());
+    let payment_service = Arc::new(
+        PaymentService::new(wechat_adapter, repository.clone()).with_config(service_config),
+    );
+    let metrics = Metrics::new();
+
+    // 启动后台任务，关闭时通过 shutdown 令牌通知其安全退出
+    let shutdown = CancellationToken::new();
+    let background_config = BackgroundConfig::from_env()?;
+    let mut background_tasks = Vec::new();
+
+    // 启动时修复上次进程中断留下的订单；跳过最近 min_age 内创建的订单，避免与其他实例进行中的下单竞争
+    if background_config.reconcile_on_startup {
+        let service = payment_service.clone();
+        let token = shutdown.child_token();
+        let limit = background_config.startup_reconcile_limit;
+        let created_before =
+            chrono::Utc::now() - chrono::Duration::from_std(background_config.reconcile_min_age)?;
+        let created_from = created_before
+            - chrono::Duration::from_std(background_config.startup_reconcile_lookback)?;
+
+        background_tasks.push(tokio::spawn(async move {
+            match service
+                .heal_interrupted_orders(created_from, created_before, limit, &token)
+                .await
+            {
+                Ok(report) => info!(
+                    "Startup reconcile healed {} orders ({} prepay recovered, {} reconciled, {} closed, {} failed, {} skipped)",
+                    report.prepay_recovered + report.reconciled + report.closed,
+                    report.prepay_recovered,
+                    report.reconciled,
+                    report.closed,
+                    report.failed,
+                    report.skipped
+                ),
+                Err(e) => error!("Startup reconcile failed: {}", e),
+            }
+        }));
+    }
+
+    if let Some(interval) = background_config.reconcile_interval {
+        let service = payment_service.clone();
+        let batch_size = background_config.reconcile_batch_size;
+        let window = chrono::Duration::from_std(background_config.reconcile_window)?;
+        let min_age = chrono::Duration::from_std(background_config.reconcile_min_age)?;
+
+        background_tasks.push(tokio::spawn(run_periodic(
+            "reconcile",
+            interval,
+            shutdown.child_token(),
+            move || {
+                let service = service.clone();
+                async move {
+                    let created_before = chrono::Utc::now() - min_age;
+                    match service
+                        .reconcile_pending(created_before, window, batch_size)
+                        .await
+                    {
+                        Ok(report) if report.reconciled == 0 => {}
+                        Ok(report) => info!(
+                            "Reconciled {} of {} pending orders across {} windows",
+                            report.reconciled, report.scanned, report.windows
+                        ),
+                        Err(e) => error!("Reconcile task failed: {}", e),
+                    }
+                }
+            },
+        )));
+    }
+
+    if let Some(interval) = background_config.outbox_relay_interval {
+        let relay = Arc::new(OutboxRelay::new(
+            Arc::new(LoggingEventPublisher::new()),
+            repository.clone(),
+            OutboxRelayConfig::from_env(),
+        ));
+        let metrics = metrics.clone();
+
+        background_tasks.push(tokio::spawn(run_periodic(
+            "outbox_relay",
+            interval,
+            shutdown.child_token(),
+            move || {
+                let relay = relay.clone();
+                let metrics = metrics.clone();
+                async move {
+                    match relay.relay_once(chrono::Utc::now()).await {
+                        Ok(report) => {
+                            metrics.set_outbox_lag(report.lag);
+                            metrics.record_outbox_events("published", report.published);
+                            metrics.record_outbox_events("retried", report.retried);
+                            metrics.record_outbox_events("poisoned", report.poisoned);
+                        }
+                        Err(e) => error!("Outbox relay failed: {}", e),
+                    }
+                }
+            },
+        )));
+    }
 
     // 创建应用状态
     let app_state = AppState {
         payment_service,
+        environment,
+        metrics,
+        admin_token: server_config.admin_token.clone(),
+        cors: CorsConfig::from_env()?,
+        response_envelope: server_config.response_envelope,
+        timeouts: TimeoutConfig::from_env()?,
+        list: ListConfig::from_env()?,
     };
 
     // 创建路由
     let app = api::create_router(app_state);
 
     // 启动服务器
-    let host = std::env::var("SERVER_HOST")
-        .unwrap_or_else(|_| "0.0.0.0".to_string());
-    let port = std::env::var("SERVER_PORT")
-        .unwrap_or_else(|_| "3000".to_string());
-    let addr = format!("{}:{}", host, port);
+    let addr = server_config.socket_addr();
 
     info!("Server listening on {}", addr);
     info!("Available endpoints:");
     info!("  GET  /health - Health check");
+    info!("  GET  /metrics - Prometheus metrics");
     info!("  POST /api/payments - Create payment");
+    info!("  GET  /api/payments - List payments (admin)");
     info!("  GET  /api/payments/:out_order_no - Query payment");
+    info!("  POST /api/payments/:out_order_no/close - Close payment");
     info!("  POST /api/webhooks/wechat - WeChat payment webhook");
+    info!("  GET  /api/admin/dead-letters - Dead-lettered notifications (admin)");
+    info!("  POST /api/admin/payments/:out_order_no/refunds - Refund payment (admin)");
+    info!(
+        "  POST /api/admin/transactions/:transaction_id/refunds - Refund by transaction id (admin)"
+    );
+    info!("  POST /api/admin/api-v3-key - Rotate api_v3_key (admin)");
+    info!("  GET  /api/admin/orders/export - Export orders as CSV (admin)");
+    if !environment.is_production() {
+        info!("  GET  /api/schema - Response examples (non-production only)");
+    }
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal(shutdown.clone()))
+        .await?;
+
+    // 等待后台任务在安全点退出
+    shutdown.cancel();
+    for task in background_tasks {
+        task.await?;
+    }
+
+    info!("Payment Service stopped");
+    Ok(())
+}
 
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+/// 等待 Ctrl+C 或 SIGTERM，然后触发关闭
+async fn shutdown_signal(shutdown: CancellationToken) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
 
-    Ok(())
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    info!("Shutdown signal received, stopping...");
+    shutdown.cancel();
 }
 
@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+
+/// 时钟端口：签名时间戳与回调新鲜度校验统一从这里取"当前时间"，测试中可注入固定时钟
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// 签名时间戳的新鲜度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Freshness {
+    /// 与当前时间的偏差在容忍范围内
+    Fresh,
+    /// 超出容忍范围（过旧或来自未来），可能是重放请求
+    Stale,
+    /// 不是合法的秒级Unix时间戳
+    Malformed,
+}
+
+impl Freshness {
+    /// 按 `clock` 校验秒级Unix时间戳，偏差不超过 `tolerance` 视为新鲜
+    pub fn check(clock: &dyn Clock, timestamp: &str, tolerance: chrono::Duration) -> Self {
+        let Some(signed_at) = timestamp
+            .parse::<i64>()
+            .ok()
+            .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        else {
+            return Freshness::Malformed;
+        };
+
+        if (clock.now() - signed_at).abs() <= tolerance {
+            Freshness::Fresh
+        } else {
+            Freshness::Stale
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct At(DateTime<Utc>);
+
+    impl Clock for At {
+        fn now(&self) -> DateTime<Utc> {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_freshness_within_tolerance_in_both_directions() {
+        let clock = At(DateTime::from_timestamp(1_703_642_400, 0).unwrap());
+        let tolerance = chrono::Duration::minutes(5);
+
+        assert_eq!(
+            Freshness::check(&clock, "1703642400", tolerance),
+            Freshness::Fresh
+        );
+        assert_eq!(
+            Freshness::check(&clock, "1703642700", tolerance),
+            Freshness::Fresh
+        );
+        assert_eq!(
+            Freshness::check(&clock, "1703642099", tolerance),
+            Freshness::Stale
+        );
+        assert_eq!(
+            Freshness::check(&clock, "1703642701", tolerance),
+            Freshness::Stale
+        );
+        assert_eq!(
+            Freshness::check(&clock, "yesterday", tolerance),
+            Freshness::Malformed
+        );
+    }
+}
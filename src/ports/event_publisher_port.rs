@@ -0,0 +1,10 @@
+use crate::domain::OutboxEvent;
+use crate::domain::errors::DomainResult;
+use async_trait::async_trait;
+
+/// 领域事件发布端口（发件箱投递目标，如消息队列）
+#[async_trait]
+pub trait EventPublisherPort: Send + Sync {
+    /// 发布事件，返回错误时由发件箱按退避策略重试
+    async fn publish(&self, event: &OutboxEvent) -> DomainResult<()>;
+}
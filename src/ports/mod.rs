@@ -1,5 +1,16 @@
+pub mod clock_port;
+pub mod event_publisher_port;
+pub mod nonce_port;
 pub mod payment_repository_port;
+pub mod secret_provider_port;
 pub mod wechat_pay_port;
 
-pub use payment_repository_port::PaymentRepositoryPort;
+pub use clock_port::{Clock, Freshness};
+pub use event_publisher_port::EventPublisherPort;
+pub use nonce_port::NonceGenerator;
+pub use payment_repository_port::{
+    OrderExportFilter, OrderListQuery, OrderPage, OrderSortField, OrderStream,
+    PaymentRepositoryPort, PendingCursor, SortDirection, UnitOfWork, WorkFuture,
+};
+pub use secret_provider_port::SecretProvider;
 pub use wechat_pay_port::*;
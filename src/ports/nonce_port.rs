@@ -0,0 +1,4 @@
+/// 随机串端口：请求签名与小程序支付参数的随机串统一从这里生成，测试中可注入固定值
+pub trait NonceGenerator: Send + Sync {
+    fn generate(&self) -> String;
+}
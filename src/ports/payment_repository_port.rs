@@ -1,10 +1,204 @@
 use crate::domain::errors::DomainResult;
-use crate::domain::PaymentOrder;
+use crate::domain::{
+    DeadLetterNotification, OutboxEvent, PaymentMethod, PaymentOrder, PaymentState, RefundRecord,
+    StateTransition, WebhookEvent,
+};
 use async_trait::async_trait;
+use futures::Stream;
+use std::future::Future;
+use std::pin::Pin;
+
+/// 订单列表可排序字段（白名单，防止通过 ORDER BY 注入）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSortField {
+    CreatedAt,
+    UpdatedAt,
+    PaidAt,
+    Amount,
+}
+
+impl OrderSortField {
+    /// 从请求参数解析，未知字段返回 None
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "created_at" => Some(OrderSortField::CreatedAt),
+            "updated_at" => Some(OrderSortField::UpdatedAt),
+            "paid_at" => Some(OrderSortField::PaidAt),
+            "amount" => Some(OrderSortField::Amount),
+            _ => None,
+        }
+    }
+
+    /// 对应的数据库列名
+    pub fn column(&self) -> &'static str {
+        match self {
+            OrderSortField::CreatedAt => "created_at",
+            OrderSortField::UpdatedAt => "updated_at",
+            OrderSortField::PaidAt => "paid_at",
+            OrderSortField::Amount => "amount_cents",
+        }
+    }
+}
+
+/// 排序方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "asc" => Some(SortDirection::Asc),
+            "desc" => Some(SortDirection::Desc),
+            _ => None,
+        }
+    }
+
+    pub fn sql(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+}
+
+/// 订单列表查询条件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderListQuery {
+    /// 按商户过滤（为 None 时不过滤）
+    pub merchant_id: Option<String>,
+    /// 按订单状态过滤
+    pub state: Option<PaymentState>,
+    /// 按支付方式过滤
+    pub payment_method: Option<PaymentMethod>,
+    /// 创建时间下限（含）
+    pub created_from: Option<chrono::DateTime<chrono::Utc>>,
+    /// 创建时间上限（不含）
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// 页码（从1开始）
+    pub page: u32,
+    /// 每页数量
+    pub page_size: u32,
+    pub sort: OrderSortField,
+    pub direction: SortDirection,
+}
+
+impl OrderListQuery {
+    /// 分页偏移量
+    pub fn offset(&self) -> u64 {
+        (self.page as u64 - 1) * self.page_size as u64
+    }
+
+    /// 订单是否满足过滤条件
+    pub fn matches(&self, order: &PaymentOrder) -> bool {
+        self.merchant_id
+            .as_ref()
+            .is_none_or(|merchant_id| &order.merchant_id == merchant_id)
+            && self.state.is_none_or(|state| order.state == state)
+            && self
+                .payment_method
+                .is_none_or(|method| order.payment_method == method)
+            && self
+                .created_from
+                .is_none_or(|from| order.created_at >= from)
+            && self
+                .created_before
+                .is_none_or(|before| order.created_at < before)
+    }
+}
+
+/// 订单分页结果
+#[derive(Debug, Clone)]
+pub struct OrderPage {
+    pub orders: Vec<PaymentOrder>,
+    pub total: u64,
+}
+
+/// 订单导出过滤条件（均为 None 时导出全部订单）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrderExportFilter {
+    pub merchant_id: Option<String>,
+    /// 创建时间下限（含）
+    pub created_from: Option<chrono::DateTime<chrono::Utc>>,
+    /// 创建时间上限（不含）
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl OrderExportFilter {
+    /// 订单是否满足过滤条件
+    pub fn matches(&self, order: &PaymentOrder) -> bool {
+        self.merchant_id
+            .as_ref()
+            .is_none_or(|merchant_id| &order.merchant_id == merchant_id)
+            && self
+                .created_from
+                .is_none_or(|from| order.created_at >= from)
+            && self
+                .created_before
+                .is_none_or(|before| order.created_at < before)
+    }
+}
+
+/// 逐行产出订单的流，调用方按需消费，无需一次性加载全部结果
+pub type OrderStream<'a> = Pin<Box<dyn Stream<Item = DomainResult<PaymentOrder>> + Send + 'a>>;
+
+/// 未完成订单的分页游标：按 (created_at, id) 升序，取严格大于该位置的订单
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingCursor {
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub id: uuid::Uuid,
+}
+
+impl PendingCursor {
+    pub fn after(order: &PaymentOrder) -> Self {
+        Self {
+            created_at: order.created_at,
+            id: order.id,
+        }
+    }
+}
+
+/// 工作单元中执行的异步操作
+pub type WorkFuture<'a, V> = Pin<Box<dyn Future<Output = DomainResult<V>> + Send + 'a>>;
+
+/// 工作单元：同一业务操作内的写操作在一个事务中执行
+///
+/// 只有调用 `commit` 后写入才会生效，未提交即释放时全部回滚。
+#[async_trait]
+pub trait UnitOfWork: Send {
+    /// 仅更新订单状态
+    async fn update_state(&mut self, order: &PaymentOrder) -> DomainResult<()>;
+
+    /// 仅更新支付结果
+    async fn set_transaction(&mut self, order: &PaymentOrder) -> DomainResult<()>;
+
+    /// 保存退款记录
+    async fn save_refund(&mut self, refund: &RefundRecord) -> DomainResult<()>;
+
+    /// 更新退款结果
+    async fn update_refund(&mut self, refund: &RefundRecord) -> DomainResult<()>;
+
+    /// 写入发件箱事件
+    async fn save_outbox_event(&mut self, event: &OutboxEvent) -> DomainResult<()>;
+
+    /// 写入订单状态变更记录
+    async fn save_transition(&mut self, transition: &StateTransition) -> DomainResult<()>;
+
+    /// 提交事务
+    async fn commit(self) -> DomainResult<()>;
+}
 
 /// 支付订单仓储端口接口
 #[async_trait]
 pub trait PaymentRepositoryPort: Send + Sync + Clone {
+    /// 工作单元类型
+    type Work: UnitOfWork;
+
+    /// 开启工作单元
+    async fn begin(&self) -> DomainResult<Self::Work>;
+
     /// 保存支付订单
     async fn save(&self, order: &PaymentOrder) -> DomainResult<()>;
 
@@ -18,9 +212,73 @@ pub trait PaymentRepositoryPort: Send + Sync + Clone {
     async fn find_by_transaction_id(&self, transaction_id: &str)
         -> DomainResult<Option<PaymentOrder>>;
 
-    /// 更新订单
-    async fn update(&self, order: &PaymentOrder) -> DomainResult<()>;
+    /// 仅更新订单状态（state、updated_at）
+    async fn update_state(&self, order: &PaymentOrder) -> DomainResult<()>;
+
+    /// 仅更新支付结果（transaction_id、paid_at、state、updated_at）
+    async fn set_transaction(&self, order: &PaymentOrder) -> DomainResult<()>;
+
+    /// 仅更新预下单结果（prepay_id、code_url、updated_at）
+    async fn set_prepay_id(&self, order: &PaymentOrder) -> DomainResult<()>;
+
+    /// 查询创建时间早于 `created_before` 的未完成订单中最早的创建时间
+    async fn oldest_pending_created_at(
+        &self,
+        created_before: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<Option<chrono::DateTime<chrono::Utc>>>;
+
+    /// 查询创建时间在 `[created_from, created_before)` 内的未完成订单（待支付或支付中），
+    /// 按 (created_at, id) 升序从游标之后取一页
+    async fn find_pending_in_window(
+        &self,
+        created_from: chrono::DateTime<chrono::Utc>,
+        created_before: chrono::DateTime<chrono::Utc>,
+        after: Option<PendingCursor>,
+        limit: u32,
+    ) -> DomainResult<Vec<PaymentOrder>>;
+
+    /// 分页查询订单
+    async fn list_orders(&self, query: &OrderListQuery) -> DomainResult<OrderPage>;
+
+    /// 按创建时间升序流式读取订单（用于大批量导出）
+    fn stream_orders(&self, filter: OrderExportFilter) -> OrderStream<'_>;
 
     /// 删除订单（软删除）
     async fn delete(&self, id: uuid::Uuid) -> DomainResult<()>;
+
+    /// 保存退款记录
+    async fn save_refund(&self, refund: &RefundRecord) -> DomainResult<()>;
+
+    /// 更新退款结果（refund_id、state、updated_at）
+    async fn update_refund(&self, refund: &RefundRecord) -> DomainResult<()>;
+
+    /// 查询订单的全部退款记录（按创建时间升序）
+    async fn find_refunds_by_order(&self, order_id: uuid::Uuid) -> DomainResult<Vec<RefundRecord>>;
+
+    /// 查询订单的全部状态变更记录（按写入顺序）
+    async fn find_transitions(&self, order_id: uuid::Uuid) -> DomainResult<Vec<StateTransition>>;
+
+    /// 保存回调通知审计记录
+    async fn save_webhook_event(&self, event: &WebhookEvent) -> DomainResult<()>;
+
+    /// 保存死信通知
+    async fn save_dead_letter(&self, letter: &DeadLetterNotification) -> DomainResult<()>;
+
+    /// 查询最近的死信通知（按时间倒序）
+    async fn list_dead_letters(&self, limit: u32) -> DomainResult<Vec<DeadLetterNotification>>;
+
+    /// 查询到期待投递的发件箱事件（按创建时间升序）
+    async fn find_due_outbox_events(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+    ) -> DomainResult<Vec<OutboxEvent>>;
+
+    /// 更新发件箱事件的投递结果
+    async fn update_outbox_event(&self, event: &OutboxEvent) -> DomainResult<()>;
+
+    /// 最早一条待投递事件的创建时间（用于计算投递延迟）
+    async fn oldest_pending_outbox_event(
+        &self,
+    ) -> DomainResult<Option<chrono::DateTime<chrono::Utc>>>;
 }
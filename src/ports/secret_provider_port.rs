@@ -0,0 +1,9 @@
+use crate::domain::errors::DomainResult;
+use async_trait::async_trait;
+
+/// 密钥提供端口：商户私钥、API v3 密钥等敏感配置从这里获取（环境变量、Vault 等）
+#[async_trait]
+pub trait SecretProvider: Send + Sync {
+    /// 按名称读取密钥，不存在时返回配置错误
+    async fn get(&self, key: &str) -> DomainResult<String>;
+}
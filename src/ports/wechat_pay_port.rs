@@ -1,5 +1,5 @@
+use crate::domain::PaymentMethod;
 use crate::domain::errors::DomainResult;
-use crate::domain::PaymentOrder;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -7,17 +7,24 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeChatPayRequest {
     pub out_order_no: String,
+    /// 支付方式，决定下单使用的 APPID
+    pub payment_method: PaymentMethod,
     pub description: String,
     pub amount_cents: i64,
     pub openid: Option<String>,
     pub client_ip: String,
     pub attach: Option<String>,
+    /// 订单优惠标记，设置时随下单请求发送
+    pub goods_tag: Option<String>,
 }
 
 /// 微信支付响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeChatPayResponse {
-    pub prepay_id: String,
+    /// 预下单ID（Native 支付不返回）
+    pub prepay_id: Option<String>,
+    /// 二维码链接（仅 Native 支付返回）
+    pub code_url: Option<String>,
 }
 
 /// 小程序支付参数
@@ -38,6 +45,62 @@ pub struct OrderQueryResponse {
     pub trade_state_desc: Option<String>,
 }
 
+/// 微信侧不存在该订单时的交易状态（下单请求从未到达微信）
+pub const TRADE_STATE_ORDER_NOT_EXIST: &str = "ORDER_NOT_EXIST";
+
+/// 回调通知签名校验结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureVerification {
+    /// 签名有效且时间戳新鲜
+    Valid,
+    /// 签名不匹配或时间戳格式错误
+    Invalid,
+    /// 时间戳超出容忍范围，可能是重放请求
+    Stale,
+}
+
+impl SignatureVerification {
+    pub fn is_valid(self) -> bool {
+        self == SignatureVerification::Valid
+    }
+
+    /// 指标标签（ok / invalid / stale）
+    pub fn label(self) -> &'static str {
+        match self {
+            SignatureVerification::Valid => "ok",
+            SignatureVerification::Invalid => "invalid",
+            SignatureVerification::Stale => "stale",
+        }
+    }
+}
+
+/// 关闭订单结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseOutcome {
+    /// 微信返回 204，订单已关闭
+    Closed,
+    /// 微信返回 ORDERPAID，订单已支付无法关闭
+    AlreadyPaid,
+}
+
+/// 退款请求参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeChatRefundRequest {
+    pub out_order_no: String,
+    pub out_refund_no: String,
+    pub reason: Option<String>,
+    pub refund_cents: i64,
+    pub total_cents: i64,
+}
+
+/// 退款响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeChatRefundResponse {
+    pub refund_id: String,
+    /// 退款状态：SUCCESS、CLOSED、PROCESSING、ABNORMAL
+    pub status: String,
+}
+
 /// 回调通知
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaymentNotification {
@@ -64,26 +127,34 @@ pub trait WeChatPayPort: Send + Sync + Clone {
         request: WeChatPayRequest,
     ) -> DomainResult<WeChatPayResponse>;
 
-    /// 生成小程序支付参数
+    /// 生成调起支付参数（按支付方式使用对应 APPID 签名）
     async fn generate_mini_pay_params(
         &self,
         prepay_id: &str,
+        payment_method: PaymentMethod,
     ) -> DomainResult<MiniProgramPayParams>;
 
     /// 查询订单
     async fn query_order(&self, out_order_no: &str) -> DomainResult<OrderQueryResponse>;
 
     /// 关闭订单
-    async fn close_order(&self, out_order_no: &str) -> DomainResult<()>;
+    async fn close_order(&self, out_order_no: &str) -> DomainResult<CloseOutcome>;
+
+    /// 申请退款
+    async fn create_refund(
+        &self,
+        request: WeChatRefundRequest,
+    ) -> DomainResult<WeChatRefundResponse>;
 
-    /// 验证回调通知签名
+    /// 验证回调通知签名（serial 为 Wechatpay-Serial 头中的平台证书序列号）
     async fn verify_notification(
         &self,
+        serial: &str,
         timestamp: &str,
         nonce: &str,
         body: &str,
         signature: &str,
-    ) -> DomainResult<bool>;
+    ) -> DomainResult<SignatureVerification>;
 
     /// 解密回调通知
     async fn decrypt_notification(
@@ -92,4 +163,7 @@ pub trait WeChatPayPort: Send + Sync + Clone {
         associated_data: &str,
         nonce: &str,
     ) -> DomainResult<String>;
+
+    /// 轮换 API v3 密钥：新密钥优先用于解密，旧密钥在过渡期内保留
+    async fn rotate_api_v3_key(&self, key: &str) -> DomainResult<()>;
 }
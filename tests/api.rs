@@ -0,0 +1,875 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use common::{ADMIN_TOKEN, TestApp, create_payment_body, json_body};
+use payment_rs::domain::PaymentState;
+use payment_rs::infrastructure::{AppEnvironment, CorsConfig, ListConfig, TimeoutConfig};
+use payment_rs::ports::PaymentRepositoryPort;
+
+#[tokio::test]
+async fn test_health_check() {
+    let app = TestApp::new();
+
+    let response = app.get("/health").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(json_body(response).await["status"], "ok");
+}
+
+#[tokio::test]
+async fn test_create_payment_returns_201() {
+    let app = TestApp::new();
+
+    let response = app
+        .post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = json_body(response).await;
+    assert_eq!(body["out_order_no"], "ORDER123");
+    assert_eq!(body["amount"], 1000);
+    assert_eq!(body["prepay_id"], "wx_mock_ORDER123");
+    assert_eq!(body["pay_params"]["package"], "prepay_id=wx_mock_ORDER123");
+    assert_eq!(body["state"], "pending");
+}
+
+#[tokio::test]
+async fn test_create_payment_invalid_amount_returns_400() {
+    let app = TestApp::new();
+    let mut body = create_payment_body("ORDER123");
+    body["amount"]["amount_cents"] = 0.into();
+
+    let response = app.post_json("/api/payments", body).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(json_body(response).await["error"], "PAYMENT_ERROR");
+}
+
+#[tokio::test]
+async fn test_create_payment_unknown_merchant_returns_400() {
+    let app = TestApp::new();
+    let mut body = create_payment_body("ORDER123");
+    body["merchant_id"] = "1900000222".into();
+
+    let response = app.post_json("/api/payments", body).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = json_body(response).await;
+    assert!(
+        body["message"]
+            .as_str()
+            .unwrap()
+            .contains("Unknown merchant id")
+    );
+    assert!(
+        app.repository
+            .find_by_out_order_no("ORDER123")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_query_payment() {
+    let app = TestApp::new();
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+
+    let response = app.get("/api/payments/ORDER123").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json_body(response).await;
+    assert_eq!(body["out_order_no"], "ORDER123");
+    assert_eq!(body["prepay_id"], "wx_mock_ORDER123");
+}
+
+#[tokio::test]
+async fn test_native_payment_qr_code_returns_png() {
+    let app = TestApp::new();
+    let mut body = create_payment_body("ORDER123");
+    body["payment_method"] = "native".into();
+    body["openid"] = serde_json::Value::Null;
+
+    let response = app.post_json("/api/payments", body).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = json_body(response).await;
+    assert_eq!(
+        body["code_url"],
+        "weixin://wxpay/bizpayurl?pr=mock_ORDER123"
+    );
+    assert!(body.get("pay_params").is_none());
+
+    let response = app.get("/api/payments/ORDER123/qrcode").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/png");
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n"));
+}
+
+#[tokio::test]
+async fn test_qr_code_for_non_native_payment_returns_409() {
+    let app = TestApp::new();
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+
+    let response = app.get("/api/payments/ORDER123/qrcode").await;
+
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    assert_eq!(json_body(response).await["error"], "QR_CODE_ERROR");
+
+    let response = app.get("/api/payments/UNKNOWN/qrcode").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_query_unknown_payment_returns_404() {
+    let app = TestApp::new();
+
+    let response = app.get("/api/payments/UNKNOWN").await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(json_body(response).await["error"], "QUERY_ERROR");
+}
+
+#[tokio::test]
+async fn test_responses_not_wrapped_by_default() {
+    let app = TestApp::new();
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+
+    let body = json_body(app.get("/api/payments/ORDER123").await).await;
+
+    assert_eq!(body["out_order_no"], "ORDER123");
+    assert!(body.get("success").is_none());
+}
+
+#[tokio::test]
+async fn test_envelope_header_wraps_success_responses() {
+    let app = TestApp::new();
+    let response = app
+        .send(
+            Request::post("/api/payments")
+                .header("Content-Type", "application/json")
+                .header("X-Response-Envelope", "true")
+                .body(Body::from(create_payment_body("ORDER123").to_string()))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = json_body(response).await;
+    assert_eq!(body["success"], true);
+    assert_eq!(body["data"]["out_order_no"], "ORDER123");
+}
+
+#[tokio::test]
+async fn test_configured_envelope_wraps_query_and_list_but_not_errors() {
+    let app = TestApp::with_response_envelope();
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+
+    let body = json_body(app.get("/api/payments/ORDER123").await).await;
+    assert_eq!(body["success"], true);
+    assert_eq!(body["data"]["out_order_no"], "ORDER123");
+
+    let body = json_body(app.get_as_admin("/api/payments").await).await;
+    assert_eq!(body["success"], true);
+    assert_eq!(body["data"]["total"], 1);
+
+    // 错误响应保持原样
+    let response = app.get("/api/payments/MISSING").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = json_body(response).await;
+    assert!(body.get("success").is_none());
+    assert_eq!(body["error"], "QUERY_ERROR");
+
+    // 健康检查与指标接口不包装
+    let body = json_body(app.get("/health").await).await;
+    assert!(body.get("success").is_none());
+}
+
+#[tokio::test]
+async fn test_slow_query_times_out_with_504() {
+    let app = TestApp::with_timeouts(TimeoutConfig {
+        query: std::time::Duration::from_millis(50),
+        ..TimeoutConfig::default()
+    });
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+    app.wechat_pay
+        .set_query_delay(std::time::Duration::from_secs(5));
+
+    let started = std::time::Instant::now();
+    let response = app.get("/api/payments/ORDER123").await;
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+
+    // 其他接口不受查询超时影响
+    let response = app.get_as_admin("/api/payments").await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_metrics_endpoint_exposes_outbox_lag() {
+    let app = TestApp::new();
+
+    let response = app.get("/metrics").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(bytes.to_vec()).unwrap();
+    assert!(body.contains("outbox_relay_lag_seconds"));
+}
+
+#[tokio::test]
+async fn test_rate_limited_query_is_marked_retryable() {
+    let app = TestApp::new();
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+    app.wechat_pay
+        .set_query_error(429, r#"{"code":"FREQUENCY_LIMITED","message":"频率超限"}"#);
+
+    let response = app.get("/api/payments/ORDER123").await;
+
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let body = json_body(response).await;
+    assert_eq!(body["retryable"], true);
+    assert_eq!(body["retry_after_ms"], 1000);
+}
+
+#[tokio::test]
+async fn test_validation_error_is_not_retryable() {
+    let app = TestApp::new();
+    let mut body = create_payment_body("ORDER123");
+    body["amount"]["amount_cents"] = 0.into();
+
+    let response = app.post_json("/api/payments", body).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = json_body(response).await;
+    assert_eq!(body["retryable"], false);
+    assert!(body["retry_after_ms"].is_null());
+}
+
+#[tokio::test]
+async fn test_webhook_missing_signature_headers_returns_400() {
+    let app = TestApp::new();
+
+    let response = app
+        .send(
+            Request::post("/api/webhooks/wechat")
+                .body(Body::from("{}"))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(json_body(response).await["error"], "INVALID_SIGNATURE");
+}
+
+#[tokio::test]
+async fn test_webhook_marks_order_succeeded() {
+    let app = TestApp::new();
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+
+    // 模拟适配器的解密直接返回密文，因此这里放入明文交易数据
+    let transaction = serde_json::json!({
+        "out_trade_no": "ORDER123",
+        "transaction_id": "TX123",
+        "trade_state": "SUCCESS"
+    });
+    let notification = serde_json::json!({
+        "id": "EV-2018022511223320873",
+        "event_type": "TRANSACTION.SUCCESS",
+        "create_time": "2023-12-27T10:00:00+08:00",
+        "resource": {
+            "algorithm": "AEAD_AES_256_GCM",
+            "ciphertext": transaction.to_string(),
+            "nonce": "fdasflkja484",
+            "associated_data": "transaction"
+        }
+    });
+
+    let response = app
+        .send(
+            Request::post("/api/webhooks/wechat")
+                .header("Wechatpay-Serial", "PUB_KEY_ID_0000000001")
+                .header("Wechatpay-Timestamp", "1703642400")
+                .header("Wechatpay-Nonce", "fdasflkja484")
+                .header("Wechatpay-Signature", "signature")
+                .body(Body::from(notification.to_string()))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(json_body(response).await["code"], "SUCCESS");
+
+    let order = app
+        .repository
+        .find_by_out_order_no("ORDER123")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(order.state, PaymentState::Succeeded);
+    assert_eq!(order.transaction_id.as_deref(), Some("TX123"));
+}
+
+#[tokio::test]
+async fn test_webhook_invalid_signature_returns_401() {
+    let app = TestApp::new();
+    app.wechat_pay.reject_signatures();
+
+    let response = app
+        .send(
+            Request::post("/api/webhooks/wechat")
+                .header("Wechatpay-Serial", "PUB_KEY_ID_0000000001")
+                .header("Wechatpay-Timestamp", "1703642400")
+                .header("Wechatpay-Nonce", "fdasflkja484")
+                .header("Wechatpay-Signature", "tampered")
+                .body(Body::from("{}"))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(json_body(response).await["error"], "INVALID_SIGNATURE");
+
+    let response = app.get("/metrics").await;
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(bytes.to_vec()).unwrap();
+    assert!(body.contains(r#"webhook_signature_verifications_total{result="invalid"} 1"#));
+}
+
+#[tokio::test]
+async fn test_webhook_before_certificates_loaded_returns_503() {
+    let app = TestApp::new();
+    app.wechat_pay.unload_certificates();
+
+    let response = app
+        .send(
+            Request::post("/api/webhooks/wechat")
+                .header("Wechatpay-Serial", "PUB_KEY_ID_0000000001")
+                .header("Wechatpay-Timestamp", "1703642400")
+                .header("Wechatpay-Nonce", "fdasflkja484")
+                .header("Wechatpay-Signature", "signature")
+                .body(Body::from("{}"))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body = json_body(response).await;
+    assert_eq!(body["error"], "CERTIFICATE_UNAVAILABLE");
+    assert_eq!(body["retryable"], true);
+}
+
+#[tokio::test]
+async fn test_webhook_for_unknown_order_is_dead_lettered_and_acked() {
+    let app = TestApp::new();
+    let transaction = serde_json::json!({
+        "out_trade_no": "UNKNOWN_ORDER",
+        "transaction_id": "TX123"
+    });
+    let notification = serde_json::json!({
+        "id": "EV-2018022511223320873",
+        "event_type": "TRANSACTION.SUCCESS",
+        "create_time": "2023-12-27T10:00:00+08:00",
+        "resource": {
+            "algorithm": "AEAD_AES_256_GCM",
+            "ciphertext": transaction.to_string(),
+            "nonce": "fdasflkja484",
+            "associated_data": "transaction"
+        }
+    });
+
+    let response = app
+        .send(
+            Request::post("/api/webhooks/wechat")
+                .header("Wechatpay-Serial", "PUB_KEY_ID_0000000001")
+                .header("Wechatpay-Timestamp", "1703642400")
+                .header("Wechatpay-Nonce", "fdasflkja484")
+                .header("Wechatpay-Signature", "signature")
+                .body(Body::from(notification.to_string()))
+                .unwrap(),
+        )
+        .await;
+
+    // 仍然应答成功，避免微信反复重试
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(json_body(response).await["code"], "SUCCESS");
+
+    let response = app
+        .send(
+            Request::get("/api/admin/dead-letters")
+                .header("Authorization", format!("Bearer {}", ADMIN_TOKEN))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let letters = json_body(response).await;
+    assert_eq!(letters.as_array().unwrap().len(), 1);
+    assert_eq!(letters[0]["notification_id"], "EV-2018022511223320873");
+    assert_eq!(letters[0]["raw_body"], notification.to_string());
+}
+
+#[tokio::test]
+async fn test_admin_endpoint_requires_token() {
+    let app = TestApp::new();
+
+    let response = app.get("/api/admin/dead-letters").await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app
+        .send(
+            Request::get("/api/admin/dead-letters")
+                .header("Authorization", "Bearer wrong-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_admin_refund_payment() {
+    let app = TestApp::new();
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+    app.wechat_pay
+        .set_query_response("SUCCESS", Some("TX123"), None);
+    app.get("/api/payments/ORDER123").await;
+
+    let refund = |uri: &str, token: Option<&str>| {
+        let mut request = Request::post(uri).header("Content-Type", "application/json");
+        if let Some(token) = token {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+        request
+            .body(Body::from(
+                r#"{"amount":{"amount_cents":500},"reason":"商品已退货"}"#,
+            ))
+            .unwrap()
+    };
+
+    // 退款只能通过管理接口发起
+    let response = app
+        .send(refund("/api/admin/payments/ORDER123/refunds", None))
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = app
+        .send(refund("/api/payments/ORDER123/refunds", Some(ADMIN_TOKEN)))
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .send(refund(
+            "/api/admin/payments/ORDER123/refunds",
+            Some(ADMIN_TOKEN),
+        ))
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = json_body(response).await;
+    assert_eq!(body["out_order_no"], "ORDER123");
+    assert_eq!(body["amount"], 500);
+}
+
+#[tokio::test]
+async fn test_refund_maps_wechat_errors() {
+    let app = TestApp::new();
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+    app.wechat_pay
+        .set_query_response("SUCCESS", Some("TX123"), None);
+    app.get("/api/payments/ORDER123").await;
+
+    let refund = |uri: &str| {
+        Request::post(uri)
+            .header("Authorization", format!("Bearer {}", ADMIN_TOKEN))
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{"amount":{"amount_cents":100}}"#))
+            .unwrap()
+    };
+
+    for (status, body, expected) in [
+        (
+            429,
+            r#"{"code":"FREQUENCY_LIMITED","message":"频率超限"}"#,
+            StatusCode::TOO_MANY_REQUESTS,
+        ),
+        (
+            500,
+            r#"{"code":"SYSTEM_ERROR","message":"系统错误"}"#,
+            StatusCode::SERVICE_UNAVAILABLE,
+        ),
+    ] {
+        app.wechat_pay.set_refund_error(status, body);
+        for uri in [
+            "/api/admin/payments/ORDER123/refunds",
+            "/api/admin/transactions/TX123/refunds",
+        ] {
+            let response = app.send(refund(uri)).await;
+            assert_eq!(response.status(), expected, "{} {}", uri, body);
+            assert_eq!(json_body(response).await["error"], "REFUND_ERROR");
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_admin_refund_by_transaction_id() {
+    let app = TestApp::new();
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+    app.wechat_pay
+        .set_query_response("SUCCESS", Some("TX123"), None);
+    app.get("/api/payments/ORDER123").await;
+
+    let refund = |transaction_id: &str| {
+        Request::post(format!(
+            "/api/admin/transactions/{}/refunds",
+            transaction_id
+        ))
+        .header("Authorization", format!("Bearer {}", ADMIN_TOKEN))
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            r#"{"amount":{"amount_cents":500},"reason":"客服退款"}"#,
+        ))
+        .unwrap()
+    };
+
+    let response = app.send(refund("TX123")).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = json_body(response).await;
+    assert_eq!(body["out_order_no"], "ORDER123");
+    assert_eq!(body["amount"], 500);
+
+    let response = app.send(refund("TX_UNKNOWN")).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_admin_rotate_api_v3_key() {
+    let app = TestApp::new();
+    let rotate = |token: &str, key: &str| {
+        Request::post("/api/admin/api-v3-key")
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "api_v3_key": key }).to_string(),
+            ))
+            .unwrap()
+    };
+    let new_key = "fedcba9876543210fedcba9876543210";
+
+    let response = app.send(rotate("wrong-token", new_key)).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app.send(rotate(ADMIN_TOKEN, "too-short")).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app.send(rotate(ADMIN_TOKEN, new_key)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json_body(response).await;
+    assert_eq!(body["fingerprint"].as_str().unwrap().len(), 16);
+    assert!(!body.to_string().contains(new_key));
+    assert_eq!(app.wechat_pay.rotated_keys(), vec![new_key]);
+}
+
+#[tokio::test]
+async fn test_admin_export_orders_as_csv() {
+    let app = TestApp::new();
+    for n in 0..3 {
+        app.post_json("/api/payments", create_payment_body(&format!("ORDER{}", n)))
+            .await;
+    }
+
+    let response = app
+        .send(
+            Request::get("/api/admin/orders/export")
+                .header("Authorization", format!("Bearer {}", ADMIN_TOKEN))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/csv; charset=utf-8"
+    );
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let csv = String::from_utf8(bytes.to_vec()).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("id,merchant_id,out_order_no"));
+    assert!(lines[1..].iter().all(|line| line.contains(",pending,")));
+}
+
+#[tokio::test]
+async fn test_webhook_invalid_body_returns_400() {
+    let app = TestApp::new();
+
+    let response = app
+        .send(
+            Request::post("/api/webhooks/wechat")
+                .header("Wechatpay-Serial", "PUB_KEY_ID_0000000001")
+                .header("Wechatpay-Timestamp", "1703642400")
+                .header("Wechatpay-Nonce", "fdasflkja484")
+                .header("Wechatpay-Signature", "signature")
+                .body(Body::from("not json"))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(json_body(response).await["error"], "INVALID_REQUEST");
+}
+
+#[tokio::test]
+async fn test_list_payments_requires_admin_token() {
+    let app = TestApp::new();
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+
+    let response = app.get("/api/payments").await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_list_payments_paginates_and_sorts() {
+    let app = TestApp::new();
+    for (out_order_no, amount) in [("ORDER1", 300), ("ORDER2", 100), ("ORDER3", 200)] {
+        let mut body = create_payment_body(out_order_no);
+        body["amount"]["amount_cents"] = amount.into();
+        app.post_json("/api/payments", body).await;
+    }
+
+    let response = app
+        .get_as_admin("/api/payments?page=1&page_size=2&sort=amount&order=asc")
+        .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json_body(response).await;
+    assert_eq!(body["total"], 3);
+    assert_eq!(body["page_size"], 2);
+    let items = body["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["out_order_no"], "ORDER2");
+    assert_eq!(items[1]["out_order_no"], "ORDER3");
+}
+
+#[tokio::test]
+async fn test_list_payments_uses_configured_page_sizes() {
+    let app = TestApp::with_list_config(ListConfig {
+        default_page_size: 2,
+        max_page_size: 3,
+    });
+    for out_order_no in ["ORDER1", "ORDER2", "ORDER3", "ORDER4"] {
+        app.post_json("/api/payments", create_payment_body(out_order_no))
+            .await;
+    }
+
+    let body = json_body(app.get_as_admin("/api/payments").await).await;
+    assert_eq!(body["page_size"], 2);
+    assert_eq!(body["items"].as_array().unwrap().len(), 2);
+
+    let body = json_body(app.get_as_admin("/api/payments?page_size=100").await).await;
+    assert_eq!(body["page_size"], 3);
+    assert_eq!(body["items"].as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_list_payments_unknown_sort_returns_400() {
+    let app = TestApp::new();
+
+    let response = app.get_as_admin("/api/payments?sort=description").await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(json_body(response).await["error"], "INVALID_LIST_PARAMS");
+}
+
+#[tokio::test]
+async fn test_list_payments_filters_by_state() {
+    let app = TestApp::new();
+    app.post_json("/api/payments", create_payment_body("ORDER1"))
+        .await;
+
+    let response = app
+        .get_as_admin("/api/payments?state=pending&created_from=2000-01-01T00:00:00Z")
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(json_body(response).await["total"], 1);
+
+    let response = app.get_as_admin("/api/payments?state=succeeded").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(json_body(response).await["total"], 0);
+}
+
+#[tokio::test]
+async fn test_list_payments_invalid_filter_names_field() {
+    let app = TestApp::new();
+
+    let response = app
+        .get_as_admin("/api/payments?created_before=yesterday")
+        .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = json_body(response).await;
+    assert_eq!(body["error"], "INVALID_LIST_PARAMS");
+    assert!(body["message"].as_str().unwrap().contains("created_before"));
+}
+
+#[tokio::test]
+async fn test_query_payment_merchant_mismatch_returns_403() {
+    let app = TestApp::new();
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+    app.wechat_pay
+        .set_query_error(400, r#"{"code":"MCH_NOT_EXISTS","message":"商户号不存在"}"#);
+
+    let response = app.get("/api/payments/ORDER123").await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let body = json_body(response).await;
+    assert!(
+        body["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Merchant mismatch")
+    );
+}
+
+#[tokio::test]
+async fn test_close_payment_merchant_mismatch_returns_403() {
+    let app = TestApp::new();
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+    app.wechat_pay
+        .set_close_error(400, r#"{"code":"MCH_NOT_EXISTS","message":"商户号不存在"}"#);
+
+    let response = app
+        .post_json("/api/payments/ORDER123/close", serde_json::json!({}))
+        .await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert!(
+        json_body(response).await["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Merchant mismatch")
+    );
+}
+
+#[tokio::test]
+async fn test_refund_merchant_mismatch_returns_403() {
+    let app = TestApp::new();
+    app.post_json("/api/payments", create_payment_body("ORDER123"))
+        .await;
+    app.wechat_pay
+        .set_query_response("SUCCESS", Some("TX123"), None);
+    app.get("/api/payments/ORDER123").await;
+    app.wechat_pay
+        .set_refund_error(400, r#"{"code":"MCH_NOT_EXISTS","message":"商户号不存在"}"#);
+
+    let response = app
+        .send(
+            Request::post("/api/admin/payments/ORDER123/refunds")
+                .header("Authorization", format!("Bearer {}", ADMIN_TOKEN))
+                .header("Content-Type", "application/json")
+                .body(Body::from(r#"{"amount":{"amount_cents":100}}"#))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let body = json_body(response).await;
+    assert_eq!(body["error"], "REFUND_ERROR");
+    assert!(
+        body["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Merchant mismatch")
+    );
+}
+
+/// 发送CORS预检请求
+async fn preflight(app: &TestApp, uri: &str, origin: &str) -> axum::http::Response<Body> {
+    app.send(
+        Request::options(uri)
+            .header("Origin", origin)
+            .header("Access-Control-Request-Method", "POST")
+            .header("Access-Control-Request-Headers", "content-type")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+}
+
+fn shop_cors() -> CorsConfig {
+    CorsConfig::parse(Some("https://shop.example.com"), None, None).unwrap()
+}
+
+#[tokio::test]
+async fn test_cors_preflight_allowed_origin() {
+    let app = TestApp::with_cors(AppEnvironment::Production, shop_cors());
+
+    let response = preflight(&app, "/api/payments", "https://shop.example.com").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "https://shop.example.com"
+    );
+}
+
+#[tokio::test]
+async fn test_cors_preflight_denied_origin() {
+    let app = TestApp::with_cors(AppEnvironment::Production, shop_cors());
+
+    let response = preflight(&app, "/api/payments", "https://evil.example.com").await;
+    assert!(
+        response
+            .headers()
+            .get("access-control-allow-origin")
+            .is_none()
+    );
+
+    // 生产环境未配置来源时拒绝所有跨域请求
+    let app = TestApp::with_cors(AppEnvironment::Production, CorsConfig::default());
+    let response = preflight(&app, "/api/payments", "https://shop.example.com").await;
+    assert!(
+        response
+            .headers()
+            .get("access-control-allow-origin")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_cors_not_applied_to_webhook() {
+    let app = TestApp::with_cors(AppEnvironment::Production, shop_cors());
+
+    let response = preflight(&app, "/api/webhooks/wechat", "https://shop.example.com").await;
+
+    assert!(
+        response
+            .headers()
+            .get("access-control-allow-origin")
+            .is_none()
+    );
+}
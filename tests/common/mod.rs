@@ -0,0 +1,168 @@
+//! 集成测试公共工具：在进程内构建完整的 axum 应用
+
+#![allow(dead_code)]
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, Response};
+use payment_rs::api::{self, AppState};
+use payment_rs::application::{PaymentService, PaymentServiceConfig};
+use payment_rs::infrastructure::{
+    AppEnvironment, CorsConfig, InMemoryPaymentRepository, ListConfig, Metrics,
+    MockWeChatPayAdapter, TimeoutConfig,
+};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// 测试用管理员令牌
+pub const ADMIN_TOKEN: &str = "test-admin-token";
+
+/// 测试用商户号（对应 WECHAT_MCHID）
+pub const MERCHANT_ID: &str = "1900000109";
+
+/// 测试应用及其依赖
+pub struct TestApp {
+    pub router: Router,
+    pub wechat_pay: Arc<MockWeChatPayAdapter>,
+    pub repository: Arc<InMemoryPaymentRepository>,
+}
+
+impl TestApp {
+    pub fn new() -> Self {
+        Self::with_cors(AppEnvironment::Development, CorsConfig::default())
+    }
+
+    /// 使用指定环境与跨域配置构建
+    pub fn with_cors(environment: AppEnvironment, cors: CorsConfig) -> Self {
+        Self::build(
+            environment,
+            cors,
+            false,
+            TimeoutConfig::default(),
+            ListConfig::default(),
+        )
+    }
+
+    /// 默认包装成功响应
+    pub fn with_response_envelope() -> Self {
+        Self::build(
+            AppEnvironment::Development,
+            CorsConfig::default(),
+            true,
+            TimeoutConfig::default(),
+            ListConfig::default(),
+        )
+    }
+
+    /// 使用指定接口超时构建
+    pub fn with_timeouts(timeouts: TimeoutConfig) -> Self {
+        Self::build(
+            AppEnvironment::Development,
+            CorsConfig::default(),
+            false,
+            timeouts,
+            ListConfig::default(),
+        )
+    }
+
+    /// 使用指定列表分页配置构建
+    pub fn with_list_config(list: ListConfig) -> Self {
+        Self::build(
+            AppEnvironment::Development,
+            CorsConfig::default(),
+            false,
+            TimeoutConfig::default(),
+            list,
+        )
+    }
+
+    fn build(
+        environment: AppEnvironment,
+        cors: CorsConfig,
+        response_envelope: bool,
+        timeouts: TimeoutConfig,
+        list: ListConfig,
+    ) -> Self {
+        let wechat_pay = Arc::new(MockWeChatPayAdapter::new());
+        let repository = Arc::new(InMemoryPaymentRepository::new());
+        let payment_service = Arc::new(
+            PaymentService::new(wechat_pay.clone(), repository.clone()).with_config(
+                PaymentServiceConfig {
+                    default_merchant_id: Some(MERCHANT_ID.to_string()),
+                    ..PaymentServiceConfig::default()
+                },
+            ),
+        );
+
+        let router = api::create_router(AppState {
+            payment_service,
+            environment,
+            metrics: Metrics::new(),
+            admin_token: Some(ADMIN_TOKEN.to_string()),
+            cors,
+            response_envelope,
+            timeouts,
+            list,
+        });
+
+        Self {
+            router,
+            wechat_pay,
+            repository,
+        }
+    }
+
+    /// 发送请求
+    pub async fn send(&self, request: Request<Body>) -> Response<Body> {
+        self.router.clone().oneshot(request).await.unwrap()
+    }
+
+    /// 发送JSON POST请求
+    pub async fn post_json(&self, uri: &str, body: serde_json::Value) -> Response<Body> {
+        self.send(
+            Request::post(uri)
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+    }
+
+    /// 发送GET请求
+    pub async fn get(&self, uri: &str) -> Response<Body> {
+        self.send(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+    }
+
+    /// 携带管理员令牌发送GET请求
+    pub async fn get_as_admin(&self, uri: &str) -> Response<Body> {
+        self.send(
+            Request::get(uri)
+                .header("Authorization", format!("Bearer {}", ADMIN_TOKEN))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+    }
+}
+
+/// 读取响应体为JSON
+pub async fn json_body(response: Response<Body>) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+/// 合法的创建支付请求体
+pub fn create_payment_body(out_order_no: &str) -> serde_json::Value {
+    serde_json::json!({
+        "merchant_id": MERCHANT_ID,
+        "out_order_no": out_order_no,
+        "amount": { "amount_cents": 1000 },
+        "payment_method": "mini_program",
+        "description": "测试商品",
+        "openid": "openid123",
+        "client_ip": "127.0.0.1"
+    })
+}